@@ -26,10 +26,16 @@ impl Card {
 
 /// Gets all cards in order (suit then rank)
 pub fn all_cards() -> Vec<Card> {
+    decks(1, &all::<Suit>().collect::<Vec<_>>())
+}
+
+/// `n` decks worth of cards in order (suit then rank), the given suits repeated to make up
+/// four per deck: two decks of spades only are eight runs of spades
+pub fn decks(n: usize, suits: &[Suit]) -> Vec<Card> {
     let mut cards = vec![];
-    for suit in all::<Suit>() {
+    for suit in suits.iter().cycle().take(4 * n) {
         for rank in all::<Rank>() {
-            cards.push(Card::new(rank, suit, Face::Up));
+            cards.push(Card::new(rank, *suit, Face::Up));
         }
     }
     cards
@@ -89,6 +95,22 @@ impl Rank {
             Self::King => Some(Self::Queen),
         }
     }
+    /// One rank up, the King going round to the Ace
+    pub fn higher_wrapping(&self) -> Self {
+        self.higher().unwrap_or(Self::Ace)
+    }
+    /// One rank down, the Ace going round to the King
+    pub fn lower_wrapping(&self) -> Self {
+        self.lower().unwrap_or(Self::King)
+    }
+    /// Whether `other` is one rank above or below, the King and Ace touching when `wrap`
+    pub fn is_adjacent(&self, other: &Self, wrap: bool) -> bool {
+        if wrap {
+            self.higher_wrapping() == *other || self.lower_wrapping() == *other
+        } else {
+            self.higher() == Some(*other) || self.lower() == Some(*other)
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Sequence, Hash)]
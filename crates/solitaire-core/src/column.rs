@@ -6,6 +6,7 @@ use crate::{
     card::{Card, Face, Rank},
     error::{CannotAcceptReason, PileError},
     pile::{PileBehavior, PileId, Side},
+    rules::{BuildOrder, ColumnRules, EmptyColumnRule},
 };
 
 /// Column of cards.
@@ -13,13 +14,20 @@ use crate::{
 pub struct Column {
     id: PileId,
     cards: VecDeque<Card>, // front = bottom, back = top
+    #[serde(default)]
+    rules: ColumnRules,
 }
 
 impl Column {
     pub fn new(id: u8, cards: Vec<Card>) -> Self {
+        Self::with_rules(id, cards, ColumnRules::default())
+    }
+    /// Creates new Column enforcing the given rules
+    pub fn with_rules(id: u8, cards: Vec<Card>, rules: ColumnRules) -> Self {
         let mut col = Self {
             id: PileId::Column(id),
             cards: VecDeque::new(),
+            rules,
         };
         for (i, card) in cards.iter().enumerate() {
             // last card face up, rest face down
@@ -32,6 +40,54 @@ impl Column {
         }
         col
     }
+    pub fn rules(&self) -> ColumnRules {
+        self.rules
+    }
+    pub fn set_rules(&mut self, rules: ColumnRules) {
+        self.rules = rules;
+    }
+    /// Face-up cards on top going down in rank in a single suit, which Spider moves as a unit
+    pub fn run_len(&self) -> usize {
+        let mut cards = self.cards.iter().rev();
+        let Some(mut above) = cards.next().filter(|c| *c.face() == Face::Up) else {
+            return 0;
+        };
+        let mut n = 1;
+        for card in cards {
+            if *card.face() == Face::Down
+                || card.suit() != above.suit()
+                || card.rank().lower() != Some(*above.rank())
+            {
+                break;
+            }
+            above = card;
+            n += 1;
+        }
+        n
+    }
+    /// Whether the top `n` cards are face up and built down by the column's rules, so they
+    /// can move together
+    pub fn is_sequence(&self, n: usize) -> bool {
+        let Some(start) = self.cards.len().checked_sub(n) else {
+            return false;
+        };
+        // Built up again from nothing, each card has to fit on the one below
+        let mut scratch = Self {
+            id: self.id,
+            cards: VecDeque::new(),
+            rules: ColumnRules {
+                empty_column: EmptyColumnRule::Any,
+                ..self.rules
+            },
+        };
+        self.cards.range(start..).all(|card| {
+            *card.face() == Face::Up && scratch.insert_card(*card, Side::Top, Face::Up).is_ok()
+        })
+    }
+    /// Whether the top 13 cards are a King to Ace run of one suit
+    pub fn has_complete_run(&self) -> bool {
+        self.run_len() >= 13
+    }
 }
 
 impl PileBehavior for Column {
@@ -52,6 +108,9 @@ impl PileBehavior for Column {
     }
 
     fn max_take_count(&self) -> usize {
+        if self.rules.suited_runs {
+            return self.run_len();
+        }
         let mut n = 0;
         for c in self.peek_all(Side::Top) {
             if *c.face() == Face::Up {
@@ -86,28 +145,53 @@ impl PileBehavior for Column {
                 });
             }
 
-            // must be alternating color
-            if card.color() == top.color() {
-                return Err(PileError::CannotAccept {
-                    pile: self.id,
-                    cards: vec![card],
-                    reason: CannotAcceptReason::WrongColor {
-                        expected: top.color().opposite(),
-                        found: card.color(),
-                    },
-                });
+            match self.rules.build_order {
+                // must be alternating color
+                BuildOrder::AlternateColor if card.color() == top.color() => {
+                    return Err(PileError::CannotAccept {
+                        pile: self.id,
+                        cards: vec![card],
+                        reason: CannotAcceptReason::WrongColor {
+                            expected: top.color().opposite(),
+                            found: card.color(),
+                        },
+                    });
+                }
+                // must be same suit
+                BuildOrder::SameSuit if card.suit() != top.suit() => {
+                    return Err(PileError::CannotAccept {
+                        pile: self.id,
+                        cards: vec![card],
+                        reason: CannotAcceptReason::WrongSuit {
+                            expected: *top.suit(),
+                            found: *card.suit(),
+                        },
+                    });
+                }
+                _ => {}
             }
         } else {
-            // empty column: must be King
-            if *card.rank() != Rank::King {
-                return Err(PileError::CannotAccept {
-                    pile: self.id,
-                    cards: vec![card],
-                    reason: CannotAcceptReason::WrongRank {
-                        expected: Rank::King,
-                        found: *card.rank(),
-                    },
-                });
+            match self.rules.empty_column {
+                EmptyColumnRule::Any => {}
+                // empty column: must be King
+                EmptyColumnRule::KingOnly if *card.rank() != Rank::King => {
+                    return Err(PileError::CannotAccept {
+                        pile: self.id,
+                        cards: vec![card],
+                        reason: CannotAcceptReason::WrongRank {
+                            expected: Rank::King,
+                            found: *card.rank(),
+                        },
+                    });
+                }
+                EmptyColumnRule::KingOnly => {}
+                EmptyColumnRule::None => {
+                    return Err(PileError::CannotAccept {
+                        pile: self.id,
+                        cards: vec![card],
+                        reason: CannotAcceptReason::Other("Empty columns cannot be filled".into()),
+                    });
+                }
             }
         }
 
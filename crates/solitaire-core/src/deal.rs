@@ -0,0 +1,122 @@
+//! Seeded deck shuffling that gives the same deal on every platform and for every
+//! version of this crate, so a deal number can be shared.
+
+use std::ops::RangeInclusive;
+
+use enum_iterator::all;
+use serde::{Deserialize, Serialize};
+
+use crate::card::{Card, Face, Rank, Suit};
+
+/// How a seed is turned into a shuffled deck.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum DealAlgorithm {
+    /// SplitMix64 driving a Fisher–Yates shuffle of the deck sorted by suit then rank
+    #[default]
+    Portable,
+    /// The deal numbers of Microsoft FreeCell (and Solitaire), which fit in 32 bits
+    Microsoft,
+}
+impl std::fmt::Display for DealAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let string = match self {
+            Self::Portable => "portable",
+            Self::Microsoft => "microsoft",
+        };
+        write!(f, "{string}")
+    }
+}
+
+impl DealAlgorithm {
+    /// Seeds this algorithm deals from, each giving its own deal
+    pub fn seeds(&self) -> RangeInclusive<u64> {
+        match self {
+            Self::Portable => 0..=u64::MAX,
+            Self::Microsoft => 0..=u32::MAX as u64,
+        }
+    }
+    /// Shuffled 52-card deck, all cards face up, in dealing order.
+    /// None if `seed` is out of `seeds()`, rather than the deal of another seed
+    pub fn shuffle(&self, seed: u64) -> Option<Vec<Card>> {
+        match self {
+            Self::Portable => self.shuffle_deck(seed, all_cards_sorted()),
+            Self::Microsoft => self.shuffle_deck(seed, microsoft_deck()),
+        }
+    }
+    /// Shuffles any `deck` (two Spider decks, say) the way `shuffle` does a single one
+    pub fn shuffle_deck(&self, seed: u64, deck: Vec<Card>) -> Option<Vec<Card>> {
+        match self {
+            Self::Portable => Some(portable_shuffle(seed, deck)),
+            Self::Microsoft => Some(microsoft_deal(u32::try_from(seed).ok()?, deck)),
+        }
+    }
+}
+
+/// SplitMix64 (Steele, Lea & Flood), small and fully specified.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+    /// Number in `0..bound`, by multiply-shift (the bias is far below 2^-50 for a deck)
+    pub fn below(&mut self, bound: usize) -> usize {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+}
+
+/// Fisher–Yates from the top: each position swaps with a random one at or below it
+fn portable_shuffle(seed: u64, mut cards: Vec<Card>) -> Vec<Card> {
+    let mut rng = SplitMix64::new(seed);
+    for i in (1..cards.len()).rev() {
+        let j = rng.below(i + 1);
+        cards.swap(i, j);
+    }
+    cards
+}
+
+/// Suits then ranks, the starting order of the portable shuffle
+fn all_cards_sorted() -> Vec<Card> {
+    let mut cards = vec![];
+    for suit in [Suit::Heart, Suit::Club, Suit::Diamond, Suit::Spade] {
+        for rank in all::<Rank>() {
+            cards.push(Card::new(rank, suit, Face::Up));
+        }
+    }
+    cards
+}
+
+/// Ranks then suits, AC AD AH AS 2C …, the starting order of Microsoft's deal
+fn microsoft_deck() -> Vec<Card> {
+    const SUITS: [Suit; 4] = [Suit::Club, Suit::Diamond, Suit::Heart, Suit::Spade];
+    all::<Rank>()
+        .flat_map(|rank| SUITS.map(|suit| Card::new(rank, suit, Face::Up)))
+        .collect()
+}
+
+/// Microsoft's deal: an MSVC `rand()` picks from the deck, each picked slot being refilled
+/// with the last card.
+fn microsoft_deal(deal_number: u32, mut deck: Vec<Card>) -> Vec<Card> {
+    let mut state = deal_number;
+    let mut rand = || {
+        state = state.wrapping_mul(214_013).wrapping_add(2_531_011) & 0x7fff_ffff;
+        (state >> 16) as usize
+    };
+
+    let mut cards = Vec::with_capacity(deck.len());
+    for left in (1..=deck.len()).rev() {
+        let j = rand() % left;
+        cards.push(deck[j]);
+        deck[j] = deck[left - 1];
+    }
+    cards
+}
@@ -47,3 +47,32 @@ pub enum PileError {
         max: usize,
     },
 }
+
+/// Error in the text notation of cards, piles and positions.
+///
+/// `line` and `column` start at 1, `column` counts characters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotationError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+impl NotationError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+impl std::error::Error for NotationError {}
+impl std::fmt::Display for NotationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
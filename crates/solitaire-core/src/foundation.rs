@@ -1,4 +1,3 @@
-use getset::Getters;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
@@ -6,19 +5,23 @@ use crate::{
     card::{Card, Face, Rank, Suit},
     error::{CannotAcceptReason, PileError},
     pile::{PileBehavior, PileId, Side},
+    rules::FoundationSuits,
 };
 
+/// Suits fixed foundations are pinned to, from the first one on
+pub const FIXED_SUITS: [Suit; 4] = [Suit::Heart, Suit::Spade, Suit::Club, Suit::Diamond];
+
 /// Foundation of cards where we take from and add to.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Getters)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Foundation {
     id: PileId,
-    #[getset(get = "pub")]
-    suit: Suit,
+    suit: Option<Suit>,    // None = unassigned until the first Ace
     cards: VecDeque<Card>, // front = bottom, back = top
 }
 
 impl Foundation {
-    pub fn new(id: u8, suit: Suit, initial_cards: Vec<Card>) -> Self {
+    /// Creates new Foundation, `suit: None` lets any Ace claim it
+    pub fn new(id: u8, suit: Option<Suit>, initial_cards: Vec<Card>) -> Self {
         let mut foundation = Self {
             id: PileId::Foundation(id),
             suit,
@@ -30,6 +33,28 @@ impl Foundation {
         }
         foundation
     }
+
+    /// Suit this foundation accepts, `None` while it is unclaimed
+    pub fn suit(&self) -> Option<Suit> {
+        self.suit.or_else(|| self.cards.front().map(|c| *c.suit()))
+    }
+    /// Suit the foundation is pinned to, `None` if the first Ace decides
+    pub fn fixed_suit(&self) -> Option<Suit> {
+        self.suit
+    }
+    /// Pins the foundation to a suit or frees it, as `suits` says. A pinned foundation
+    /// keeps the suit it already holds, an empty one takes its place in `FIXED_SUITS`
+    pub fn set_suits(&mut self, suits: FoundationSuits) {
+        let PileId::Foundation(i) = self.id else {
+            return;
+        };
+        self.suit = match suits {
+            FoundationSuits::Fixed => self
+                .suit()
+                .or(Some(FIXED_SUITS[i as usize % FIXED_SUITS.len()])),
+            FoundationSuits::Any => None,
+        };
+    }
 }
 
 impl PileBehavior for Foundation {
@@ -54,22 +79,24 @@ impl PileBehavior for Foundation {
     }
 
     fn insert_card(&mut self, mut card: Card, _side: Side, _face: Face) -> Result<(), PileError> {
+        // Suit must match foundation (if claimed)
+        if let Some(suit) = self.suit()
+            && *card.suit() != suit
+        {
+            return Err(PileError::CannotAccept {
+                pile: self.id,
+                cards: vec![card],
+                reason: CannotAcceptReason::WrongSuit {
+                    expected: suit,
+                    found: *card.suit(),
+                },
+            });
+        }
+
         // Only one card at a time
         if !self.cards.is_empty() {
             let top_card = self.cards.back().unwrap();
 
-            // Suit must match foundation
-            if *card.suit() != self.suit {
-                return Err(PileError::CannotAccept {
-                    pile: self.id,
-                    cards: vec![card],
-                    reason: CannotAcceptReason::WrongSuit {
-                        expected: self.suit,
-                        found: *card.suit(),
-                    },
-                });
-            }
-
             // Rank must be next in sequence
             if let Some(next_rank) = top_card.rank().higher() {
                 if *card.rank() != next_rank {
@@ -90,17 +117,6 @@ impl PileBehavior for Foundation {
                 });
             }
         } else {
-            // Suit must match foundation
-            if *card.suit() != self.suit {
-                return Err(PileError::CannotAccept {
-                    pile: self.id,
-                    cards: vec![card],
-                    reason: CannotAcceptReason::WrongSuit {
-                        expected: self.suit,
-                        found: *card.suit(),
-                    },
-                });
-            }
             // Empty foundation must start with Ace
             if *card.rank() != Rank::Ace {
                 return Err(PileError::CannotAccept {
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::{
+    card::{Card, Face},
+    error::PileError,
+    pile::{PileBehavior, PileId, Side},
+};
+
+/// FreeCell cell parking a single card of any kind.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FreeCell {
+    id: PileId,
+    cards: VecDeque<Card>, // at most one
+}
+
+impl FreeCell {
+    pub fn new(id: u8) -> Self {
+        Self {
+            id: PileId::Cell(id),
+            cards: VecDeque::new(),
+        }
+    }
+}
+
+impl PileBehavior for FreeCell {
+    fn id(&self) -> PileId {
+        self.id
+    }
+
+    fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    fn max_take_count(&self) -> usize {
+        self.cards.len()
+    }
+
+    fn cards(&self) -> &VecDeque<Card> {
+        &self.cards
+    }
+
+    fn cards_mut(&mut self) -> &mut VecDeque<Card> {
+        &mut self.cards
+    }
+
+    fn insert_card(&mut self, mut card: Card, _side: Side, face: Face) -> Result<(), PileError> {
+        if !self.cards.is_empty() {
+            return Err(PileError::InvalidPlaceTooMany {
+                pile: self.id,
+                attempted: 1,
+                max: 0,
+            });
+        }
+        card.set_face(face);
+        self.cards.push_back(card);
+        Ok(())
+    }
+}
@@ -1,17 +1,27 @@
 pub mod card;
 pub mod column;
+pub mod deal;
 pub mod error;
 pub mod foundation;
+pub mod free_cell;
+pub mod notation;
 pub mod pile;
+pub mod pyramid;
+pub mod rules;
 pub mod stock;
 pub mod waste;
 
 pub mod prelude {
     pub use crate::card::*;
     pub use crate::column::*;
+    pub use crate::deal::*;
     pub use crate::error::*;
     pub use crate::foundation::*;
+    pub use crate::free_cell::*;
+    pub use crate::notation::*;
     pub use crate::pile::*;
+    pub use crate::pyramid::*;
+    pub use crate::rules::*;
     pub use crate::stock::*;
     pub use crate::waste::*;
 }
@@ -0,0 +1,476 @@
+//! Compact text notation for cards and piles.
+//!
+//! A card is its rank (`A`, `2`…`10`, `J`, `Q`, `K`) followed by its suit (`h`, `c`, `d`, `s`),
+//! prefixed with `#` when face down: `Ah`, `10s`, `#Kd`. A pile is one line, its label then
+//! its cards from bottom to top: `c3: #Kd #5h Qs Jh`. Labels are `stock`, `waste`, `f0`…,
+//! `c0`…, `cell0`… and `p0`…, a foundation pinned to a suit shows it as `f0(h)`.
+
+use enum_iterator::all;
+use std::{fmt, str::FromStr};
+
+use crate::{
+    card::{Card, Face, Rank, Suit},
+    column::Column,
+    error::NotationError,
+    foundation::Foundation,
+    free_cell::FreeCell,
+    pile::{PileBehavior, PileId, Side},
+    pyramid::PyramidSlot,
+    rules::{BuildOrder, EmptyColumnRule, FoundationSuits, PassLimit, RuleSet, SuitCount, Variant},
+    stock::Stock,
+    waste::Waste,
+};
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rank::Ace => write!(f, "A"),
+            Rank::Jack => write!(f, "J"),
+            Rank::Queen => write!(f, "Q"),
+            Rank::King => write!(f, "K"),
+            rank => write!(f, "{}", *rank as u8),
+        }
+    }
+}
+
+fn suit_char(suit: Suit) -> char {
+    match suit {
+        Suit::Heart => 'h',
+        Suit::Club => 'c',
+        Suit::Diamond => 'd',
+        Suit::Spade => 's',
+    }
+}
+fn parse_suit(s: &str) -> Option<Suit> {
+    match s {
+        "h" | "H" => Some(Suit::Heart),
+        "c" | "C" => Some(Suit::Club),
+        "d" | "D" => Some(Suit::Diamond),
+        "s" | "S" => Some(Suit::Spade),
+        _ => None,
+    }
+}
+fn parse_rank(s: &str) -> Option<Rank> {
+    let rank = match s {
+        "A" | "a" | "1" => Rank::Ace,
+        "2" => Rank::Two,
+        "3" => Rank::Three,
+        "4" => Rank::Four,
+        "5" => Rank::Five,
+        "6" => Rank::Six,
+        "7" => Rank::Seven,
+        "8" => Rank::Eight,
+        "9" => Rank::Nine,
+        "10" | "T" | "t" => Rank::Ten,
+        "J" | "j" => Rank::Jack,
+        "Q" | "q" => Rank::Queen,
+        "K" | "k" => Rank::King,
+        _ => return None,
+    };
+    Some(rank)
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if *self.face() == Face::Down {
+            write!(f, "#")?;
+        }
+        write!(f, "{}{}", self.rank(), suit_char(*self.suit()))
+    }
+}
+impl FromStr for Card {
+    type Err = NotationError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_card(s).map_err(|message| NotationError::new(1, 1, message))
+    }
+}
+fn parse_card(token: &str) -> Result<Card, String> {
+    let (face, rest) = match token.strip_prefix('#') {
+        Some(rest) => (Face::Down, rest),
+        None => (Face::Up, token),
+    };
+    let Some(split) = rest.char_indices().last().map(|(i, _)| i) else {
+        return Err("expected a card".to_string());
+    };
+    let (rank, suit) = rest.split_at(split);
+    let rank = parse_rank(rank).ok_or_else(|| format!("unknown rank in '{token}'"))?;
+    let suit = parse_suit(suit).ok_or_else(|| format!("unknown suit in '{token}'"))?;
+    Ok(Card::new(rank, suit, face))
+}
+
+/// Whitespace separated tokens with the character column (from 1) where each starts
+fn tokens(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut column = 0;
+    let mut start = None;
+    let mut out = vec![];
+    for (byte, c) in s.char_indices() {
+        column += 1;
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some((byte, column)),
+            (true, Some((b, col))) => {
+                out.push((col, &s[b..byte]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((b, col)) = start {
+        out.push((col, &s[b..]));
+    }
+    out.into_iter()
+}
+
+/// Cards of `s`, `line` and `first_column` locate `s` for errors
+pub fn parse_cards(s: &str, line: usize, first_column: usize) -> Result<Vec<Card>, NotationError> {
+    tokens(s)
+        .map(|(column, token)| {
+            parse_card(token)
+                .map_err(|message| NotationError::new(line, first_column + column - 1, message))
+        })
+        .collect()
+}
+
+/// One pile line split into its label and its cards (bottom to top)
+pub fn parse_pile_line(text: &str, line: usize) -> Result<(&str, Vec<Card>), NotationError> {
+    let Some((label, cards)) = text.split_once(':') else {
+        return Err(NotationError::new(line, 1, "expected 'label: cards'"));
+    };
+    let first_column = label.chars().count() + 2;
+    Ok((label.trim(), parse_cards(cards, line, first_column)?))
+}
+
+impl PileId {
+    /// Short name used by the notation: `stock`, `waste`, `f0`, `c0`, `cell0`, `p0`…
+    pub fn label(&self) -> String {
+        match self {
+            PileId::Stock => "stock".to_string(),
+            PileId::Waste => "waste".to_string(),
+            PileId::Column(i) => format!("c{i}"),
+            PileId::Foundation(i) => format!("f{i}"),
+            PileId::Cell(i) => format!("cell{i}"),
+            PileId::Pyramid(i) => format!("p{i}"),
+        }
+    }
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "stock" => Some(PileId::Stock),
+            "waste" => Some(PileId::Waste),
+            _ if label.starts_with("cell") => label[4..].parse().ok().map(PileId::Cell),
+            _ => match label.split_at_checked(1)? {
+                ("c", i) => i.parse().ok().map(PileId::Column),
+                ("f", i) => i.parse().ok().map(PileId::Foundation),
+                ("p", i) => i.parse().ok().map(PileId::Pyramid),
+                _ => None,
+            },
+        }
+    }
+}
+
+fn write_pile(f: &mut fmt::Formatter<'_>, label: &str, pile: &impl PileBehavior) -> fmt::Result {
+    write!(f, "{label}:")?;
+    for card in pile.cards() {
+        write!(f, " {card}")?;
+    }
+    Ok(())
+}
+/// Label and cards of a single pile line, checking the label is `expected`
+fn parse_single_pile(s: &str, expected: &str) -> Result<Vec<Card>, NotationError> {
+    let (label, cards) = parse_pile_line(s.trim_end(), 1)?;
+    if label != expected {
+        return Err(NotationError::new(
+            1,
+            1,
+            format!("expected '{expected}', got '{label}'"),
+        ));
+    }
+    Ok(cards)
+}
+/// Puts `cards` on `pile` as they are, face included, without checking any rule
+pub fn fill_pile(pile: &mut impl PileBehavior, cards: Vec<Card>) {
+    for card in cards {
+        pile.raw_insert_card(card, Side::Top, *card.face());
+    }
+}
+
+impl fmt::Display for Stock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_pile(f, "stock", self)
+    }
+}
+impl FromStr for Stock {
+    type Err = NotationError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut stock = Stock::new(vec![]);
+        fill_pile(&mut stock, parse_single_pile(s, "stock")?);
+        Ok(stock)
+    }
+}
+
+impl fmt::Display for Waste {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_pile(f, "waste", self)
+    }
+}
+impl FromStr for Waste {
+    type Err = NotationError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut waste = Waste::new(vec![]);
+        fill_pile(&mut waste, parse_single_pile(s, "waste")?);
+        waste.reset_fan();
+        Ok(waste)
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_pile(f, &self.id().label(), self)
+    }
+}
+impl FromStr for Column {
+    type Err = NotationError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (label, cards) = parse_pile_line(s.trim_end(), 1)?;
+        let Some(PileId::Column(id)) = PileId::from_label(label) else {
+            return Err(NotationError::new(
+                1,
+                1,
+                format!("expected a column, got '{label}'"),
+            ));
+        };
+        let mut column = Column::new(id, vec![]);
+        fill_pile(&mut column, cards);
+        Ok(column)
+    }
+}
+
+impl fmt::Display for FreeCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_pile(f, &self.id().label(), self)
+    }
+}
+impl FromStr for FreeCell {
+    type Err = NotationError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (label, cards) = parse_pile_line(s.trim_end(), 1)?;
+        free_cell_from_parts(label, cards).map_err(|message| NotationError::new(1, 1, message))
+    }
+}
+/// Free cell from a label such as `cell1` and its card
+pub fn free_cell_from_parts(label: &str, cards: Vec<Card>) -> Result<FreeCell, String> {
+    let Some(PileId::Cell(id)) = PileId::from_label(label) else {
+        return Err(format!("expected a free cell, got '{label}'"));
+    };
+    if cards.len() > 1 {
+        return Err(format!("{label} holds a single card"));
+    }
+    let mut cell = FreeCell::new(id);
+    fill_pile(&mut cell, cards);
+    Ok(cell)
+}
+
+impl fmt::Display for PyramidSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_pile(f, &self.id().label(), self)
+    }
+}
+impl FromStr for PyramidSlot {
+    type Err = NotationError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (label, cards) = parse_pile_line(s.trim_end(), 1)?;
+        pyramid_slot_from_parts(label, cards).map_err(|message| NotationError::new(1, 1, message))
+    }
+}
+/// Pyramid slot from a label such as `p12` and its card
+pub fn pyramid_slot_from_parts(label: &str, cards: Vec<Card>) -> Result<PyramidSlot, String> {
+    let Some(PileId::Pyramid(id)) = PileId::from_label(label) else {
+        return Err(format!("expected a pyramid slot, got '{label}'"));
+    };
+    if cards.len() > 1 {
+        return Err(format!("{label} holds a single card"));
+    }
+    let mut slot = PyramidSlot::new(id);
+    fill_pile(&mut slot, cards);
+    Ok(slot)
+}
+
+impl fmt::Display for Foundation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut label = self.id().label();
+        if let Some(suit) = self.fixed_suit() {
+            label.push_str(&format!("({})", suit_char(suit)));
+        }
+        write_pile(f, &label, self)
+    }
+}
+impl FromStr for Foundation {
+    type Err = NotationError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (label, cards) = parse_pile_line(s.trim_end(), 1)?;
+        foundation_from_parts(label, cards).map_err(|message| NotationError::new(1, 1, message))
+    }
+}
+/// Foundation from a label such as `f2` or `f2(s)` and its cards
+pub fn foundation_from_parts(label: &str, cards: Vec<Card>) -> Result<Foundation, String> {
+    let (id, suit) = match label.split_once('(') {
+        Some((id, suit)) => {
+            let suit = suit
+                .strip_suffix(')')
+                .and_then(parse_suit)
+                .ok_or_else(|| format!("invalid foundation suit in '{label}'"))?;
+            (id, Some(suit))
+        }
+        None => (label, None),
+    };
+    let Some(PileId::Foundation(id)) = PileId::from_label(id) else {
+        return Err(format!("expected a foundation, got '{label}'"));
+    };
+    let mut foundation = Foundation::new(id, suit, vec![]);
+    fill_pile(&mut foundation, cards);
+    Ok(foundation)
+}
+
+/// Value of the `variant` rule
+fn variant_key(variant: Variant) -> &'static str {
+    match variant {
+        Variant::Klondike => "klondike",
+        Variant::FreeCell => "freecell",
+        Variant::Spider => "spider",
+        Variant::Yukon => "yukon",
+        Variant::Russian => "russian",
+        Variant::Pyramid => "pyramid",
+        Variant::Golf => "golf",
+        Variant::TriPeaks => "tripeaks",
+    }
+}
+
+impl RuleSet {
+    /// Rules as `key=value` pairs, e.g.
+    /// `draw=1 passes=unlimited build=alternate empty=king foundations=any to-tableau=yes`,
+    /// led by `variant=…` for games other than Klondike, `suits=…` for Spider and `wrap=…`
+    /// for Golf and TriPeaks
+    pub fn to_notation(&self) -> String {
+        let variant = match self.variant {
+            Variant::Klondike => String::new(),
+            Variant::Spider => {
+                let suits = match self.suits {
+                    SuitCount::One => 1,
+                    SuitCount::Two => 2,
+                    SuitCount::Four => 4,
+                };
+                format!("variant=spider suits={suits} ")
+            }
+            variant if variant.builds_on_waste() => format!(
+                "variant={} wrap={} ",
+                variant_key(variant),
+                if self.wrap { "yes" } else { "no" }
+            ),
+            variant => format!("variant={} ", variant_key(variant)),
+        };
+        format!(
+            "{variant}draw={} passes={} build={} empty={} foundations={} to-tableau={}",
+            self.draw_count,
+            match self.pass_limit {
+                PassLimit::Unlimited => "unlimited".to_string(),
+                PassLimit::Passes(n) => n.to_string(),
+            },
+            match self.build_order {
+                BuildOrder::AlternateColor => "alternate",
+                BuildOrder::SameSuit => "same",
+                BuildOrder::AnySuit => "any",
+            },
+            match self.empty_column {
+                EmptyColumnRule::Any => "any",
+                EmptyColumnRule::KingOnly => "king",
+                EmptyColumnRule::None => "none",
+            },
+            match self.foundation_suits {
+                FoundationSuits::Fixed => "fixed",
+                FoundationSuits::Any => "any",
+            },
+            if self.foundation_to_tableau {
+                "yes"
+            } else {
+                "no"
+            },
+        )
+    }
+    /// Reads `to_notation` output, missing keys keep the default of the variant, which has to
+    /// come first if given
+    pub fn from_notation(s: &str, line: usize, first_column: usize) -> Result<Self, NotationError> {
+        let mut rules = RuleSet::default();
+        for (i, (column, pair)) in tokens(s).enumerate() {
+            let error =
+                |message: String| NotationError::new(line, first_column + column - 1, message);
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| error(format!("expected key=value, got '{pair}'")))?;
+            let invalid = || error(format!("invalid value '{value}' for {key}"));
+            match key {
+                // The variant brings its own defaults, which would undo the keys before it
+                "variant" if i > 0 => {
+                    return Err(error("variant has to be the first rule".to_string()));
+                }
+                "variant" => {
+                    let variant = all::<Variant>()
+                        .find(|v| variant_key(*v) == value)
+                        .ok_or_else(invalid)?;
+                    rules = RuleSet::for_variant(variant);
+                }
+                "suits" => {
+                    rules.suits = match value {
+                        "1" => SuitCount::One,
+                        "2" => SuitCount::Two,
+                        "4" => SuitCount::Four,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "wrap" => {
+                    rules.wrap = match value {
+                        "yes" => true,
+                        "no" => false,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "draw" => rules.draw_count = value.parse().map_err(|_| invalid())?,
+                "passes" => {
+                    rules.pass_limit = match value {
+                        "unlimited" => PassLimit::Unlimited,
+                        n => PassLimit::Passes(n.parse().map_err(|_| invalid())?),
+                    }
+                }
+                "build" => {
+                    rules.build_order = match value {
+                        "alternate" => BuildOrder::AlternateColor,
+                        "same" => BuildOrder::SameSuit,
+                        "any" => BuildOrder::AnySuit,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "empty" => {
+                    rules.empty_column = match value {
+                        "any" => EmptyColumnRule::Any,
+                        "king" => EmptyColumnRule::KingOnly,
+                        "none" => EmptyColumnRule::None,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "foundations" => {
+                    rules.foundation_suits = match value {
+                        "fixed" => FoundationSuits::Fixed,
+                        "any" => FoundationSuits::Any,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "to-tableau" => {
+                    rules.foundation_to_tableau = match value {
+                        "yes" => true,
+                        "no" => false,
+                        _ => return Err(invalid()),
+                    }
+                }
+                _ => return Err(error(format!("unknown rule '{key}'"))),
+            }
+        }
+        Ok(rules)
+    }
+}
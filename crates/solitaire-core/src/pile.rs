@@ -5,21 +5,32 @@ use serde::{Deserialize, Serialize};
 use crate::{
     card::{Card, Face},
     error::PileError,
+    rules::Variant,
 };
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
 pub enum PileId {
     Stock,
     Waste,
-    Column(u8),     // 0..=6
-    Foundation(u8), // 0..=3
+    Column(u8),     // 0..=6 in Klondike, up to 9 in Spider
+    Foundation(u8), // 0..=3, 0..=7 in Spider
+    Cell(u8),       // 0..=3, FreeCell only
+    Pyramid(u8),    // 0..=27, row by row from the top
 }
 impl PileId {
+    /// Whether the pile exists in Klondike
     pub fn is_valid(&self) -> bool {
+        self.is_valid_in(Variant::Klondike)
+    }
+    /// Whether the pile exists in `variant`
+    pub fn is_valid_in(&self, variant: Variant) -> bool {
         match self {
-            PileId::Stock | PileId::Waste => true,
-            PileId::Column(i) => *i <= 6,
-            PileId::Foundation(i) => *i <= 3,
+            PileId::Stock => variant.has_stock(),
+            PileId::Waste => variant.has_waste(),
+            PileId::Column(i) => *i < variant.n_columns(),
+            PileId::Foundation(i) => *i < variant.n_foundations(),
+            PileId::Cell(i) => *i < variant.n_cells(),
+            PileId::Pyramid(i) => *i < variant.n_pyramid_slots(),
         }
     }
 }
@@ -30,6 +41,8 @@ impl std::fmt::Display for PileId {
             PileId::Waste => write!(f, "waste"),
             PileId::Column(i) => write!(f, "column {}", i),
             PileId::Foundation(i) => write!(f, "foundation {}", i),
+            PileId::Cell(i) => write!(f, "cell {}", i),
+            PileId::Pyramid(i) => write!(f, "pyramid {}", i),
         }
     }
 }
@@ -0,0 +1,150 @@
+//! Pyramid of 28 overlapping cards: seven rows from the single card at the top, each card
+//! held by its own slot and covered by the two cards of the row below.
+//!
+//! TriPeaks deals the same 28 slots as three peaks of 3, 6, 9 and 10 cards a row.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::{
+    card::{Card, Face, Rank},
+    error::PileError,
+    pile::{PileBehavior, PileId, Side},
+};
+
+pub const PYRAMID_ROWS: u8 = 7;
+pub const PYRAMID_SIZE: u8 = PYRAMID_ROWS * (PYRAMID_ROWS + 1) / 2;
+
+/// Row (0 at the top) and position in that row of slot `i`, counting row by row
+pub fn pyramid_row(i: u8) -> (u8, u8) {
+    let mut row = 0;
+    let mut first = 0;
+    while i > first + row {
+        first += row + 1;
+        row += 1;
+    }
+    (row, i - first)
+}
+
+/// Slot at position `pos` of `row`
+pub fn pyramid_slot(row: u8, pos: u8) -> u8 {
+    row * (row + 1) / 2 + pos
+}
+
+/// The two slots overlapping slot `i`, none on the bottom row
+pub fn covering_slots(i: u8) -> Vec<u8> {
+    let (row, pos) = pyramid_row(i);
+    if row + 1 >= PYRAMID_ROWS {
+        return vec![];
+    }
+    vec![pyramid_slot(row + 1, pos), pyramid_slot(row + 1, pos + 1)]
+}
+
+/// Cards in each row of the three TriPeaks peaks, top row first
+pub const PEAKS_ROWS: [u8; 4] = [3, 6, 9, 10];
+pub const PEAKS_SIZE: u8 = 28;
+
+/// Row (0 at the tips) and position in that row of TriPeaks slot `i`
+pub fn peaks_row(i: u8) -> (u8, u8) {
+    let mut first = 0;
+    for (row, len) in PEAKS_ROWS.into_iter().enumerate() {
+        if i < first + len {
+            return (row as u8, i - first);
+        }
+        first += len;
+    }
+    (PEAKS_ROWS.len() as u8, i - first)
+}
+
+/// TriPeaks slot at position `pos` of `row`
+pub fn peaks_slot(row: u8, pos: u8) -> u8 {
+    PEAKS_ROWS[..row as usize].iter().sum::<u8>() + pos
+}
+
+/// The two TriPeaks slots overlapping slot `i`, none on the bottom row
+pub fn peaks_covering_slots(i: u8) -> Vec<u8> {
+    let (row, pos) = peaks_row(i);
+    // Leftmost of the two, the peaks only join on the bottom rows
+    let left = match row {
+        0 => 2 * pos,
+        1 => 3 * (pos / 2) + pos % 2,
+        2 => pos,
+        _ => return vec![],
+    };
+    vec![peaks_slot(row + 1, left), peaks_slot(row + 1, left + 1)]
+}
+
+/// Row of TriPeaks slot `i` and its distance from the left edge in half cards
+pub fn peaks_position(i: u8) -> (u8, u8) {
+    let (row, pos) = peaks_row(i);
+    let half = match row {
+        0 => 6 * pos + 3,
+        1 => 6 * (pos / 2) + 2 * (pos % 2) + 2,
+        2 => 2 * pos + 1,
+        _ => 2 * pos,
+    };
+    (row, half)
+}
+
+/// Value a card counts for in a pair: Ace 1 up to King 13
+pub fn pair_value(card: &Card) -> u8 {
+    *card.rank() as u8
+}
+/// Whether `first` and `second` (or a King alone) can be removed together
+pub fn is_pair(first: &Card, second: Option<&Card>) -> bool {
+    match second {
+        Some(second) => pair_value(first) + pair_value(second) == 13,
+        None => *first.rank() == Rank::King,
+    }
+}
+
+/// One position of the pyramid, holding a card until it is paired off.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PyramidSlot {
+    id: PileId,
+    cards: VecDeque<Card>, // at most one
+}
+
+impl PyramidSlot {
+    pub fn new(id: u8) -> Self {
+        Self {
+            id: PileId::Pyramid(id),
+            cards: VecDeque::new(),
+        }
+    }
+}
+
+impl PileBehavior for PyramidSlot {
+    fn id(&self) -> PileId {
+        self.id
+    }
+
+    fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    fn max_take_count(&self) -> usize {
+        self.cards.len()
+    }
+
+    fn cards(&self) -> &VecDeque<Card> {
+        &self.cards
+    }
+
+    fn cards_mut(&mut self) -> &mut VecDeque<Card> {
+        &mut self.cards
+    }
+
+    fn insert_card(&mut self, mut card: Card, _side: Side, face: Face) -> Result<(), PileError> {
+        if !self.cards.is_empty() {
+            return Err(PileError::InvalidPlaceTooMany {
+                pile: self.id,
+                attempted: 1,
+                max: 0,
+            });
+        }
+        card.set_face(face);
+        self.cards.push_back(card);
+        Ok(())
+    }
+}
@@ -0,0 +1,381 @@
+use enum_iterator::{Sequence, all};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    card::{Card, Suit, all_cards, decks},
+    pyramid::{self, PEAKS_SIZE, PYRAMID_ROWS, PYRAMID_SIZE},
+};
+
+/// House rules a game is played with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleSet {
+    /// Game being played, which decides the piles and how they are dealt
+    pub variant: Variant,
+    /// Which cards may be placed on an empty column
+    pub empty_column: EmptyColumnRule,
+    /// How cards are built down on columns
+    pub build_order: BuildOrder,
+    /// Whether cards may be moved back from a foundation onto a column
+    pub foundation_to_tableau: bool,
+    /// Whether foundations are pinned to a suit or claimed by the first Ace
+    pub foundation_suits: FoundationSuits,
+    /// Number of cards moved from stock to waste per draw
+    pub draw_count: usize,
+    /// Maximum number of passes through the stock
+    pub pass_limit: PassLimit,
+    /// Suits the Spider decks are made of
+    pub suits: SuitCount,
+    /// Whether a King and an Ace follow each other when building on the waste
+    pub wrap: bool,
+}
+impl Default for RuleSet {
+    /// Classic Klondike, draw one, unlimited passes
+    fn default() -> Self {
+        Self {
+            variant: Variant::Klondike,
+            empty_column: EmptyColumnRule::default(),
+            build_order: BuildOrder::default(),
+            foundation_to_tableau: true,
+            foundation_suits: FoundationSuits::default(),
+            draw_count: 1,
+            pass_limit: PassLimit::default(),
+            suits: SuitCount::default(),
+            wrap: false,
+        }
+    }
+}
+impl RuleSet {
+    /// Usual rules of `variant`
+    pub fn for_variant(variant: Variant) -> Self {
+        match variant {
+            Variant::Klondike => Self::default(),
+            Variant::FreeCell => Self {
+                variant,
+                empty_column: EmptyColumnRule::Any,
+                foundation_to_tableau: false,
+                ..Self::default()
+            },
+            Variant::Spider => Self {
+                variant,
+                empty_column: EmptyColumnRule::Any,
+                build_order: BuildOrder::AnySuit,
+                foundation_to_tableau: false,
+                pass_limit: PassLimit::Passes(1),
+                ..Self::default()
+            },
+            Variant::Yukon => Self {
+                variant,
+                ..Self::default()
+            },
+            Variant::Russian => Self {
+                variant,
+                build_order: BuildOrder::SameSuit,
+                ..Self::default()
+            },
+            Variant::Pyramid => Self {
+                variant,
+                foundation_to_tableau: false,
+                pass_limit: PassLimit::Passes(3),
+                ..Self::default()
+            },
+            Variant::Golf => Self {
+                variant,
+                foundation_to_tableau: false,
+                pass_limit: PassLimit::Passes(1),
+                ..Self::default()
+            },
+            Variant::TriPeaks => Self {
+                variant,
+                foundation_to_tableau: false,
+                pass_limit: PassLimit::Passes(1),
+                wrap: true,
+                ..Self::default()
+            },
+        }
+    }
+    /// Every card the game is played with, face up, in order
+    pub fn deck(&self) -> Vec<Card> {
+        match self.variant {
+            Variant::Spider => decks(2, &self.suits.suits()),
+            _ => all_cards(),
+        }
+    }
+}
+impl std::fmt::Display for RuleSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.variant.has_stock() {
+            return write!(
+                f,
+                "{}, {}, empty column: {}",
+                self.variant, self.build_order, self.empty_column
+            );
+        }
+        if self.variant == Variant::Spider {
+            return write!(f, "Spider, {}", self.suits);
+        }
+        if self.variant == Variant::Pyramid {
+            return write!(f, "Pyramid, {}", self.pass_limit);
+        }
+        if self.variant.builds_on_waste() {
+            write!(f, "{}", self.variant)?;
+            if self.wrap {
+                write!(f, ", King and Ace wrap")?;
+            }
+            return Ok(());
+        }
+        write!(
+            f,
+            "Draw {}, {}, {}, empty column: {}, foundation suits: {}",
+            self.draw_count,
+            self.pass_limit,
+            self.build_order,
+            self.empty_column,
+            self.foundation_suits
+        )?;
+        if !self.foundation_to_tableau {
+            write!(f, ", no foundation to tableau")?;
+        }
+        Ok(())
+    }
+}
+
+/// Solitaire game, each with its own piles and deal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, Sequence)]
+pub enum Variant {
+    #[default]
+    Klondike,
+    FreeCell,
+    Spider,
+    Yukon,
+    /// Yukon building in suit
+    Russian,
+    Pyramid,
+    Golf,
+    /// Three overlapping peaks, cleared onto the waste
+    TriPeaks,
+}
+impl std::fmt::Display for Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let string = match self {
+            Self::Klondike => "Klondike",
+            Self::FreeCell => "FreeCell",
+            Self::Spider => "Spider",
+            Self::Yukon => "Yukon",
+            Self::Russian => "Russian",
+            Self::Pyramid => "Pyramid",
+            Self::Golf => "Golf",
+            Self::TriPeaks => "TriPeaks",
+        };
+        write!(f, "{string}")
+    }
+}
+impl Variant {
+    pub fn n_columns(&self) -> u8 {
+        match self {
+            Self::Klondike | Self::Yukon | Self::Russian | Self::Golf => 7,
+            Self::FreeCell => 8,
+            Self::Spider => 10,
+            Self::Pyramid | Self::TriPeaks => 0,
+        }
+    }
+    /// Free cells, each holding a single card
+    pub fn n_cells(&self) -> u8 {
+        match self {
+            Self::FreeCell => 4,
+            _ => 0,
+        }
+    }
+    /// Slots of the overlapping pyramid, or of the three peaks in TriPeaks
+    pub fn n_pyramid_slots(&self) -> u8 {
+        match self {
+            Self::Pyramid => PYRAMID_SIZE,
+            Self::TriPeaks => PEAKS_SIZE,
+            _ => 0,
+        }
+    }
+    /// Slots overlapping slot `i` from the row below, all gone once it is free
+    pub fn covering_slots(&self, i: u8) -> Vec<u8> {
+        match self {
+            Self::TriPeaks => pyramid::peaks_covering_slots(i),
+            _ => pyramid::covering_slots(i),
+        }
+    }
+    /// Where slot `i` is drawn: its row, and how far from the left edge in half cards
+    pub fn slot_position(&self, i: u8) -> (u8, u8) {
+        match self {
+            Self::TriPeaks => pyramid::peaks_position(i),
+            _ => {
+                let (row, pos) = pyramid::pyramid_row(i);
+                (row, PYRAMID_ROWS - 1 - row + 2 * pos)
+            }
+        }
+    }
+    pub fn n_foundations(&self) -> u8 {
+        match self {
+            Self::Spider => 8,
+            Self::Klondike | Self::FreeCell | Self::Yukon | Self::Russian => 4,
+            // Where paired cards are discarded
+            Self::Pyramid => 1,
+            // Cards go to the waste instead
+            Self::Golf | Self::TriPeaks => 0,
+        }
+    }
+    /// Whether undealt cards wait in a stock
+    pub fn has_stock(&self) -> bool {
+        match self {
+            Self::Klondike | Self::Spider | Self::Pyramid | Self::Golf | Self::TriPeaks => true,
+            Self::FreeCell | Self::Yukon | Self::Russian => false,
+        }
+    }
+    /// Whether the stock is turned over onto a waste, rather than dealt onto the columns
+    pub fn has_waste(&self) -> bool {
+        matches!(
+            self,
+            Self::Klondike | Self::Pyramid | Self::Golf | Self::TriPeaks
+        )
+    }
+    /// Cards moved onto a foundation at once: one, or a whole King to Ace run in Spider
+    pub fn foundation_move_size(&self) -> usize {
+        match self {
+            Self::Spider => 13,
+            _ => 1,
+        }
+    }
+    /// Whether cards leave by pairs adding up to 13 rather than by moves between piles
+    pub fn removes_pairs(&self) -> bool {
+        *self == Self::Pyramid
+    }
+    /// Whether tableau cards are played onto the waste, one rank above or below its top card
+    pub fn builds_on_waste(&self) -> bool {
+        matches!(self, Self::Golf | Self::TriPeaks)
+    }
+    /// Whether any face-up group moves as long as its bottom card fits, in any order above
+    pub fn moves_unordered_groups(&self) -> bool {
+        matches!(self, Self::Yukon | Self::Russian)
+    }
+}
+
+/// Number of different suits in play, fewer make Spider easier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, Sequence)]
+pub enum SuitCount {
+    One,
+    Two,
+    #[default]
+    Four,
+}
+impl std::fmt::Display for SuitCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let string = match self {
+            Self::One => "one suit",
+            Self::Two => "two suits",
+            Self::Four => "four suits",
+        };
+        write!(f, "{string}")
+    }
+}
+impl SuitCount {
+    /// Suits in play
+    pub fn suits(&self) -> Vec<Suit> {
+        match self {
+            Self::One => vec![Suit::Spade],
+            Self::Two => vec![Suit::Spade, Suit::Heart],
+            Self::Four => all::<Suit>().collect(),
+        }
+    }
+}
+
+/// The part of a `RuleSet` a column enforces on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColumnRules {
+    pub empty_column: EmptyColumnRule,
+    pub build_order: BuildOrder,
+    /// Only runs of one suit move together, as in Spider
+    pub suited_runs: bool,
+}
+impl RuleSet {
+    /// Rules the columns of this game check when cards are put on them or taken off
+    pub fn column_rules(&self) -> ColumnRules {
+        ColumnRules {
+            empty_column: self.empty_column,
+            build_order: self.build_order,
+            suited_runs: self.variant == Variant::Spider,
+        }
+    }
+}
+
+/// Which cards may be placed on an empty column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum EmptyColumnRule {
+    Any,
+    #[default]
+    KingOnly,
+    None,
+}
+impl std::fmt::Display for EmptyColumnRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let string = match self {
+            Self::Any => "any card",
+            Self::KingOnly => "king only",
+            Self::None => "none",
+        };
+        write!(f, "{string}")
+    }
+}
+
+/// How cards are built down on columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum BuildOrder {
+    #[default]
+    AlternateColor,
+    SameSuit,
+    AnySuit,
+}
+impl std::fmt::Display for BuildOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let string = match self {
+            Self::AlternateColor => "alternate color",
+            Self::SameSuit => "same suit",
+            Self::AnySuit => "any suit",
+        };
+        write!(f, "{string}")
+    }
+}
+
+/// How suits are assigned to foundations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum FoundationSuits {
+    /// Each foundation only accepts its own suit
+    Fixed,
+    /// Any Ace may start any empty foundation
+    #[default]
+    Any,
+}
+impl std::fmt::Display for FoundationSuits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let string = match self {
+            Self::Fixed => "fixed",
+            Self::Any => "any",
+        };
+        write!(f, "{string}")
+    }
+}
+
+/// How many times the player may go through the stock
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum PassLimit {
+    #[default]
+    Unlimited,
+    /// Total number of passes, the initial one included (so `Passes(1)` never recycles)
+    Passes(u32),
+}
+impl std::fmt::Display for PassLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PassLimit::Unlimited => write!(f, "unlimited"),
+            PassLimit::Passes(1) => write!(f, "1 pass"),
+            PassLimit::Passes(n) => write!(f, "{n} passes"),
+        }
+    }
+}
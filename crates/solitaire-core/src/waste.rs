@@ -12,6 +12,8 @@ use serde::{Deserialize, Serialize};
 pub struct Waste {
     id: PileId,
     cards: VecDeque<Card>, // front = bottom, back = top
+    #[serde(default)]
+    fanned: usize, // number of top cards fanned out by the last draw
 }
 
 impl Waste {
@@ -19,12 +21,29 @@ impl Waste {
         let mut waste = Self {
             id: PileId::Waste,
             cards: VecDeque::new(),
+            fanned: 0,
         };
         for card in cards {
             waste.insert_card(card, Side::Top, Face::Up).unwrap();
         }
+        waste.fanned = 0;
         waste
     }
+
+    /// Number of top cards currently fanned out (only the topmost one is playable)
+    pub fn fanned(&self) -> usize {
+        self.fanned.min(self.cards.len())
+    }
+
+    /// Squares up the fan so the next draw starts a new one
+    pub fn reset_fan(&mut self) {
+        self.fanned = 0;
+    }
+
+    /// Restores a previously saved fan, e.g. when undoing
+    pub fn set_fanned(&mut self, fanned: usize) {
+        self.fanned = fanned;
+    }
 }
 
 impl PileBehavior for Waste {
@@ -48,9 +67,38 @@ impl PileBehavior for Waste {
         &mut self.cards
     }
 
+    fn raw_insert_card(&mut self, mut card: Card, side: Side, face: Face) {
+        card.set_face(face);
+        match side {
+            Side::Top => {
+                self.cards.push_back(card);
+                self.fanned += 1;
+            }
+            Side::Bottom => self.cards.push_front(card),
+        }
+    }
+
+    fn take_card(&mut self, side: Side) -> Result<Card, PileError> {
+        let card = match side {
+            Side::Top => self.cards.pop_back(),
+            Side::Bottom => self.cards.pop_front(),
+        };
+        let card = card.ok_or(PileError::NotEnoughCards {
+            pile: self.id,
+            requested: 1,
+            available: 0,
+        })?;
+        // Taking the top card uncovers the next one in the fan
+        if side == Side::Top {
+            self.fanned = self.fanned.saturating_sub(1);
+        }
+        Ok(card)
+    }
+
     fn insert_card(&mut self, mut card: Card, _side: Side, _face: Face) -> Result<(), PileError> {
         card.set_face(Face::Up); // Waste cards are always face up
         self.cards.push_back(card); // insert on top/back
+        self.fanned += 1;
         Ok(())
     }
 }
@@ -0,0 +1,103 @@
+use solitaire_core::prelude::*;
+
+/// Two-character card names as used by deal listings, e.g. "TS" for the ten of spades
+fn names(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(|card| {
+            let rank = match card.rank() {
+                Rank::Ace => 'A',
+                Rank::Ten => 'T',
+                Rank::Jack => 'J',
+                Rank::Queen => 'Q',
+                Rank::King => 'K',
+                r => char::from_digit(*r as u32, 10).unwrap(),
+            };
+            let suit = match card.suit() {
+                Suit::Club => 'C',
+                Suit::Diamond => 'D',
+                Suit::Heart => 'H',
+                Suit::Spade => 'S',
+            };
+            format!("{rank}{suit}")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[test]
+fn portable_deals_are_pinned() {
+    let golden = [
+        (
+            0,
+            "KH TD 9H TS 3H JS KS QH 3D KC 6C AC JC 3S 4H QS 4D 2C JD AH 5H 5D KD 6S 8D 5S 8S QC TH 4S AS 7D 2D 7H QD 5C 7C AD 9C 8C 6D 4C 2S JH 9D 8H 3C 6H 9S 2H TC 7S",
+        ),
+        (
+            1,
+            "9S 7S QD 4S AH 8D QS JD 6H 5D 7C JH 3C 7D QH AS TH 5H 2D 8H 5S KC TC AD 5C AC 2H 9H 4H 2C 6D 3H KS 8S 3D 6S 7H 3S 8C 6C QC 4C 9D KH JC 2S TD JS 9C TS KD 4D",
+        ),
+        (
+            42,
+            "JS 5H 6C 3S 4S AH TH 6H KS 2D QC TS JC 7S 8D 9S 7H QH 5S KH 9C 3D 3C 7D QD 7C 6S 6D 4D 9D 3H 5D TC TD 5C 4H 8H KC AS 8S 8C QS AD 2C JD JH 2S 2H 4C AC 9H KD",
+        ),
+        (
+            123_456_789,
+            "9H 6S 3C KH 2C 5S 2D 2H 8D 4D KS 9S 4S JH AD 2S TC 6D TD AC AS 6C 3H JS 3D 7S AH QH 8S 7C 4C 5D 8C 5C 6H 3S QD 4H 8H 9C JC JD TS QS 9D KD 7D 5H KC TH QC 7H",
+        ),
+    ];
+    for (seed, deck) in golden {
+        assert_eq!(
+            names(&DealAlgorithm::Portable.shuffle(seed).unwrap()),
+            deck,
+            "seed {seed}"
+        );
+    }
+}
+
+#[test]
+fn microsoft_deal_numbers_match_freecell() {
+    let golden = [
+        (
+            1,
+            "JD 2D 9H JC 5D 7H 7C 5H KD KC 9S 5S AD QC KH 3H 2S KS 9D QD JS AS AH 3C 4C 5C TS QH 4H AC 4D 7S 3S TD 4S TH 8H 2C JH 7D 6D 8S 8D QS 6C 3D 8C TC 6S 9C 2H 6H",
+        ),
+        (
+            617,
+            "7D AD 5C 3S 5S 8C 2D AH TD 7S QD AC 6D 8H AS KH TH QC 3H 9D 6S 8D 3D TC KD 5H 9S 3C 8S 7H 4D JS 4C QS 9C 9H 7C 6H 2C 2S 4S TS 2H 5D JC 6C JH QH JD KS KC 4H",
+        ),
+    ];
+    for (deal_number, deck) in golden {
+        assert_eq!(
+            names(&DealAlgorithm::Microsoft.shuffle(deal_number).unwrap()),
+            deck,
+            "deal {deal_number}"
+        );
+    }
+}
+
+#[test]
+fn every_deal_is_a_full_deck() {
+    for algorithm in [DealAlgorithm::Portable, DealAlgorithm::Microsoft] {
+        for seed in 0..50 {
+            let mut deck = algorithm.shuffle(seed).unwrap();
+            deck.sort_by_key(|c| (*c.suit() as u8, *c.rank()));
+            let mut expected = all_cards();
+            expected.sort_by_key(|c| (*c.suit() as u8, *c.rank()));
+            assert_eq!(deck, expected);
+        }
+    }
+}
+
+#[test]
+fn microsoft_seeds_fit_in_32_bits() {
+    let seeds = DealAlgorithm::Microsoft.seeds();
+    assert!(seeds.contains(&(u32::MAX as u64)));
+    assert!(!seeds.contains(&(u32::MAX as u64 + 1)));
+    assert_eq!(DealAlgorithm::Portable.seeds().end(), &u64::MAX);
+    // A seed past 32 bits is not quietly dealt as another deal number
+    assert!(
+        DealAlgorithm::Microsoft
+            .shuffle(u32::MAX as u64 + 1)
+            .is_none()
+    );
+}
@@ -0,0 +1,141 @@
+use solitaire_core::prelude::*;
+
+#[test]
+fn cards_round_trip() {
+    for face in [Face::Up, Face::Down] {
+        for mut card in all_cards() {
+            card.set_face(face);
+            let text = card.to_string();
+            assert_eq!(text.parse::<Card>().unwrap(), card, "{text}");
+        }
+    }
+}
+
+#[test]
+fn card_examples() {
+    let ace = Card::new(Rank::Ace, Suit::Heart, Face::Up);
+    assert_eq!(ace.to_string(), "Ah");
+    assert_eq!(
+        Card::new(Rank::Ten, Suit::Spade, Face::Up).to_string(),
+        "10s"
+    );
+    assert_eq!(
+        Card::new(Rank::King, Suit::Diamond, Face::Down).to_string(),
+        "#Kd"
+    );
+    assert_eq!("AH".parse::<Card>().unwrap(), ace);
+    assert_eq!(
+        "Ts".parse::<Card>().unwrap(),
+        Card::new(Rank::Ten, Suit::Spade, Face::Up)
+    );
+    assert!("11h".parse::<Card>().is_err());
+    assert!("Ax".parse::<Card>().is_err());
+    assert!("#".parse::<Card>().is_err());
+}
+
+#[test]
+fn piles_round_trip() {
+    let column: Column = "c3: #Kd #5h Qs Jh".parse().unwrap();
+    assert_eq!(column.id(), PileId::Column(3));
+    assert_eq!(column.len(), 4);
+    assert_eq!(column.to_string(), "c3: #Kd #5h Qs Jh");
+
+    let foundation: Foundation = "f1(s): As 2s".parse().unwrap();
+    assert_eq!(foundation.fixed_suit(), Some(Suit::Spade));
+    assert_eq!(foundation.to_string(), "f1(s): As 2s");
+    let foundation: Foundation = "f2:".parse().unwrap();
+    assert_eq!(foundation.to_string(), "f2:");
+
+    let stock: Stock = "stock: #2c #3c".parse().unwrap();
+    assert_eq!(stock.to_string(), "stock: #2c #3c");
+    let waste: Waste = "waste: 9d".parse().unwrap();
+    assert_eq!(waste.to_string(), "waste: 9d");
+}
+
+#[test]
+fn errors_point_at_the_bad_token() {
+    let error = "c0: Kd Qx".parse::<Column>().err().unwrap();
+    assert_eq!((error.line, error.column), (1, 8));
+    let error = "stock: 2c".parse::<Waste>().err().unwrap();
+    assert_eq!((error.line, error.column), (1, 1));
+}
+
+#[test]
+fn rules_round_trip() {
+    let rules = RuleSet {
+        draw_count: 3,
+        pass_limit: PassLimit::Passes(2),
+        build_order: BuildOrder::SameSuit,
+        empty_column: EmptyColumnRule::Any,
+        foundation_suits: FoundationSuits::Fixed,
+        foundation_to_tableau: false,
+        ..RuleSet::default()
+    };
+    assert_eq!(
+        RuleSet::from_notation(&rules.to_notation(), 1, 1).unwrap(),
+        rules
+    );
+}
+
+#[test]
+fn free_cells_and_variants() {
+    let cell: FreeCell = "cell2: Qh".parse().unwrap();
+    assert_eq!(cell.id(), PileId::Cell(2));
+    assert_eq!(cell.to_string(), "cell2: Qh");
+    assert!("cell2: Qh Js".parse::<FreeCell>().is_err());
+    assert_eq!(PileId::from_label("c2"), Some(PileId::Column(2)));
+
+    let rules = RuleSet::for_variant(Variant::FreeCell);
+    let text = rules.to_notation();
+    assert!(text.starts_with("variant=freecell "), "{text}");
+    assert_eq!(RuleSet::from_notation(&text, 1, 1).unwrap(), rules);
+    // Klondike rules read the same as before variants existed
+    assert!(!RuleSet::default().to_notation().contains("variant"));
+
+    assert!(PileId::Column(7).is_valid_in(Variant::FreeCell));
+    assert!(!PileId::Column(7).is_valid());
+    assert!(!PileId::Stock.is_valid_in(Variant::FreeCell));
+}
+
+#[test]
+fn spider_rules_keep_the_suit_count() {
+    let rules = RuleSet {
+        suits: SuitCount::Two,
+        ..RuleSet::for_variant(Variant::Spider)
+    };
+    let text = rules.to_notation();
+    assert!(text.starts_with("variant=spider suits=2 "), "{text}");
+    assert_eq!(RuleSet::from_notation(&text, 1, 1).unwrap(), rules);
+    assert!(RuleSet::from_notation("suits=3", 1, 1).is_err());
+
+    let deck = decks(2, &SuitCount::Two.suits());
+    assert_eq!(deck.len(), 104);
+    assert_eq!(deck.iter().filter(|c| *c.suit() == Suit::Heart).count(), 52);
+    assert_eq!(decks(1, &SuitCount::Four.suits()), all_cards());
+    assert!(PileId::Column(9).is_valid_in(Variant::Spider));
+    assert!(!PileId::Waste.is_valid_in(Variant::Spider));
+}
+
+#[test]
+fn every_variant_reads_back() {
+    for variant in enum_iterator::all::<Variant>() {
+        let rules = RuleSet::for_variant(variant);
+        assert_eq!(
+            RuleSet::from_notation(&rules.to_notation(), 1, 1).unwrap(),
+            rules,
+            "{variant}"
+        );
+    }
+}
+
+#[test]
+fn variant_comes_before_the_rules_it_sets() {
+    let rules = RuleSet::from_notation("variant=spider suits=2 draw=3", 1, 1).unwrap();
+    assert_eq!(rules.variant, Variant::Spider);
+    assert_eq!(rules.suits, SuitCount::Two);
+    assert_eq!(rules.draw_count, 3);
+
+    // Read after `draw`, the variant would quietly put it back to its own default
+    let error = RuleSet::from_notation("draw=3 variant=spider", 4, 8).unwrap_err();
+    assert_eq!((error.line, error.column), (4, 15));
+}
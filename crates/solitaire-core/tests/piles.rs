@@ -0,0 +1,127 @@
+use solitaire_core::prelude::*;
+
+fn card(text: &str) -> Card {
+    text.parse().unwrap()
+}
+
+/// Column holding `cards` face up, under `rules`
+fn column(rules: ColumnRules, cards: &[&str]) -> Column {
+    let mut column = Column::with_rules(0, vec![], rules);
+    for text in cards {
+        column.raw_insert_card(card(text), Side::Top, Face::Up);
+    }
+    column
+}
+
+fn accepts(column: &Column, text: &str) -> bool {
+    column
+        .clone()
+        .insert_card(card(text), Side::Top, Face::Up)
+        .is_ok()
+}
+
+#[test]
+fn build_order_decides_what_goes_on_a_card() {
+    let rules = |build_order| ColumnRules {
+        build_order,
+        ..ColumnRules::default()
+    };
+    let alternate = column(rules(BuildOrder::AlternateColor), &["9s"]);
+    assert!(accepts(&alternate, "8h"));
+    assert!(!accepts(&alternate, "8c"));
+    assert!(!accepts(&alternate, "7h"));
+
+    let same_suit = column(rules(BuildOrder::SameSuit), &["9s"]);
+    assert!(accepts(&same_suit, "8s"));
+    assert!(!accepts(&same_suit, "8c"));
+    assert!(!accepts(&same_suit, "8h"));
+
+    let any_suit = column(rules(BuildOrder::AnySuit), &["9s"]);
+    assert!(accepts(&any_suit, "8s"));
+    assert!(accepts(&any_suit, "8h"));
+    assert!(!accepts(&any_suit, "10h"));
+}
+
+#[test]
+fn empty_column_rule_decides_what_fills_a_gap() {
+    let rules = |empty_column| ColumnRules {
+        empty_column,
+        ..ColumnRules::default()
+    };
+    let any = column(rules(EmptyColumnRule::Any), &[]);
+    assert!(accepts(&any, "5d"));
+    assert!(accepts(&any, "Kd"));
+
+    let kings = column(rules(EmptyColumnRule::KingOnly), &[]);
+    assert!(accepts(&kings, "Kd"));
+    assert!(!accepts(&kings, "Qd"));
+
+    let none = column(rules(EmptyColumnRule::None), &[]);
+    assert!(!accepts(&none, "Kd"));
+}
+
+#[test]
+fn columns_keep_only_their_own_rules() {
+    let rules = RuleSet {
+        build_order: BuildOrder::SameSuit,
+        empty_column: EmptyColumnRule::Any,
+        draw_count: 3,
+        ..RuleSet::default()
+    };
+    let column_rules = rules.column_rules();
+    assert_eq!(column_rules.build_order, BuildOrder::SameSuit);
+    assert_eq!(column_rules.empty_column, EmptyColumnRule::Any);
+    assert!(!column_rules.suited_runs);
+    assert!(
+        RuleSet::for_variant(Variant::Spider)
+            .column_rules()
+            .suited_runs
+    );
+}
+
+#[test]
+fn any_ace_claims_an_empty_foundation() {
+    for ace in ["Ah", "Ac", "Ad", "As"] {
+        let mut foundation = Foundation::new(0, None, vec![]);
+        assert_eq!(foundation.suit(), None);
+        foundation
+            .insert_card(card(ace), Side::Top, Face::Up)
+            .unwrap();
+        assert_eq!(foundation.suit(), Some(*card(ace).suit()));
+        assert_eq!(foundation.fixed_suit(), None);
+        // The claimed suit is the only one it takes from then on
+        let takes_two = foundation
+            .clone()
+            .insert_card(card("2d"), Side::Top, Face::Up)
+            .is_ok();
+        assert_eq!(takes_two, ace == "Ad", "{ace}");
+    }
+}
+
+#[test]
+fn fixed_foundations_refuse_other_suits() {
+    let mut foundation = Foundation::new(0, Some(Suit::Heart), vec![]);
+    assert!(matches!(
+        foundation.insert_card(card("As"), Side::Top, Face::Up),
+        Err(PileError::CannotAccept {
+            reason: CannotAcceptReason::WrongSuit {
+                expected: Suit::Heart,
+                found: Suit::Spade,
+            },
+            ..
+        })
+    ));
+    foundation
+        .insert_card(card("Ah"), Side::Top, Face::Up)
+        .unwrap();
+
+    // Rules pin an empty foundation to its place in the fixed order
+    let mut foundation = Foundation::new(1, None, vec![]);
+    foundation.set_suits(FoundationSuits::Fixed);
+    assert_eq!(foundation.fixed_suit(), Some(FIXED_SUITS[1]));
+    assert!(
+        foundation
+            .insert_card(card("Ah"), Side::Top, Face::Up)
+            .is_err()
+    );
+}
@@ -11,13 +11,19 @@ pub enum Action {
         from: PileId,
         to: PileId,
     },
+    /// Pyramid: discards two available cards adding up to 13, or a King alone
+    RemovePair {
+        first: PileId,
+        second: Option<PileId>,
+    },
     Undo,
+    Redo,
     Reset,
 }
 impl Display for Action {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let string = match &self {
-            Action::Draw => "Drew a card from stock".to_string(),
+            Action::Draw => "Drew from stock".to_string(),
             Action::Recycle => "Recycled waste into stock".to_string(),
             Action::Move {
                 num_cards,
@@ -26,7 +32,16 @@ impl Display for Action {
             } => {
                 format!("Moved {num_cards} card(s) from {from} to {to}")
             }
+            Action::RemovePair {
+                first,
+                second: Some(second),
+            } => format!("Removed the pair from {first} and {second}"),
+            Action::RemovePair {
+                first,
+                second: None,
+            } => format!("Removed the King from {first}"),
             Action::Undo => "Undid last action".to_string(),
+            Action::Redo => "Redid last undone action".to_string(),
             Action::Reset => "Reset game".to_string(),
         };
         write!(f, "{string}")
@@ -1,74 +1,137 @@
-use getset::Getters;
+use getset::{CopyGetters, Getters};
 use serde::{Deserialize, Serialize};
 use solitaire_core::pile::PileId;
 
-use crate::{action::Action, error::GameError, game::Game, prelude::GameState};
+use crate::{action::Action, error::GameError, game::Game, hint::Hint, prelude::GameState};
 
-#[derive(Clone, Serialize, Deserialize, Getters)]
+#[derive(Clone, Serialize, Deserialize, Getters, CopyGetters)]
 pub struct ActionsResults {
     #[getset(get = "pub")]
     data: Vec<(Action, Result<GameState, GameError>)>,
+    /// Waste cards fanned out by the last draw, of which only the top one is ever tried
+    #[serde(default)]
+    #[getset(get_copy = "pub")]
+    fanned: usize,
 }
 impl ActionsResults {
     pub fn empty() -> Self {
-        Self { data: vec![] }
+        Self {
+            data: vec![],
+            fanned: 0,
+        }
     }
+    /// Results for the current position of `game`, its waste fan included
     pub fn from_game(game: &Game) -> Self {
+        Self::from_state(&game.state)
+    }
+    /// Tries every candidate action on a copy of `state` (history is never cloned)
+    pub fn from_state(state: &GameState) -> Self {
         let mut res = vec![];
         // Draw
         {
-            let game_new = game.clone();
             let action = Action::Draw;
-            let result = game_new.test_action(action.clone());
+            let result = state.test_action(&action);
             res.push((action, result));
         }
         // Recycle
         {
-            let game_new = game.clone();
             let action = Action::Recycle;
-            let result = game_new.test_action(action.clone());
+            let result = state.test_action(&action);
             res.push((action, result));
         }
 
         // Waste to foundations
         {
-            for pile_id in game.state.foundation_ids() {
-                let game_new = game.clone();
+            for pile_id in state.foundation_ids() {
                 let action = Action::Move {
                     num_cards: 1,
                     from: PileId::Waste,
                     to: pile_id,
                 };
-                let result = game_new.test_action(action.clone());
+                let result = state.test_action(&action);
                 res.push((action, result));
             }
         }
 
         // Waste to columns
         {
-            for pile_id in game.state.column_ids() {
-                let game_new = game.clone();
+            for pile_id in state.column_ids() {
                 let action = Action::Move {
                     num_cards: 1,
                     from: PileId::Waste,
                     to: pile_id,
                 };
-                let result = game_new.test_action(action.clone());
+                let result = state.test_action(&action);
                 res.push((action, result));
             }
         }
 
         // Foundation to columns
         {
-            for f_pile_id in game.state.foundation_ids() {
-                for c_pile_id in game.state.column_ids() {
-                    let game_new = game.clone();
+            for f_pile_id in state.foundation_ids() {
+                for c_pile_id in state.column_ids() {
                     let action = Action::Move {
                         num_cards: 1,
                         from: f_pile_id,
                         to: c_pile_id,
                     };
-                    let result = game_new.test_action(action.clone());
+                    let result = state.test_action(&action);
+                    res.push((action, result));
+                }
+            }
+        }
+
+        // Cells to (foundations, columns)
+        {
+            for from_pile_id in state.cell_ids() {
+                for to_pile_id in state.foundation_ids().into_iter().chain(state.column_ids()) {
+                    let action = Action::Move {
+                        num_cards: 1,
+                        from: from_pile_id,
+                        to: to_pile_id,
+                    };
+                    let result = state.test_action(&action);
+                    res.push((action, result));
+                }
+            }
+        }
+
+        // Column tops to cells
+        {
+            for from_pile_id in state.column_ids() {
+                for to_pile_id in state.cell_ids() {
+                    let action = Action::Move {
+                        num_cards: 1,
+                        from: from_pile_id,
+                        to: to_pile_id,
+                    };
+                    let result = state.test_action(&action);
+                    res.push((action, result));
+                }
+            }
+        }
+
+        // Golf and TriPeaks: column tops and free peak cards onto the waste
+        if state.rules.variant.builds_on_waste() {
+            for from_pile_id in state.column_ids().into_iter().chain(state.pyramid_ids()) {
+                let action = Action::Move {
+                    num_cards: 1,
+                    from: from_pile_id,
+                    to: PileId::Waste,
+                };
+                let result = state.test_action(&action);
+                res.push((action, result));
+            }
+        }
+
+        // Pyramid pairs and Kings among the available cards
+        {
+            let available = state.available_piles();
+            for (i, &first) in available.iter().enumerate() {
+                let seconds = available[i + 1..].iter().map(|&second| Some(second));
+                for second in std::iter::once(None).chain(seconds) {
+                    let action = Action::RemovePair { first, second };
+                    let result = state.test_action(&action);
                     res.push((action, result));
                 }
             }
@@ -76,8 +139,8 @@ impl ActionsResults {
 
         // Columns to (foundations, columns)
         {
-            for from_pile_id in game.state.column_ids() {
-                if let Ok(from_column) = game.state.pile(from_pile_id) {
+            for from_pile_id in state.column_ids() {
+                if let Ok(from_column) = state.pile(from_pile_id) {
                     let column_len = from_column.len();
 
                     // Try all possible stacks
@@ -90,29 +153,27 @@ impl ActionsResults {
                         }
 
                         // --- To Foundations ---
-                        for to_pile_id in game.state.foundation_ids() {
-                            let game_new = game.clone();
+                        for to_pile_id in state.foundation_ids() {
                             let action = Action::Move {
                                 num_cards: n,
                                 from: from_pile_id,
                                 to: to_pile_id,
                             };
-                            let result = game_new.test_action(action.clone());
+                            let result = state.test_action(&action);
                             res.push((action, result));
                         }
 
                         // --- To other Columns ---
-                        for to_pile_id in game.state.column_ids() {
+                        for to_pile_id in state.column_ids() {
                             if to_pile_id == from_pile_id {
                                 continue; // skip same column
                             }
-                            let game_new = game.clone();
                             let action = Action::Move {
                                 num_cards: n,
                                 from: from_pile_id,
                                 to: to_pile_id,
                             };
-                            let result = game_new.test_action(action.clone());
+                            let result = state.test_action(&action);
                             res.push((action, result));
                         }
                     }
@@ -120,7 +181,10 @@ impl ActionsResults {
             }
         }
 
-        Self { data: res }
+        Self {
+            data: res,
+            fanned: state.waste.fanned(),
+        }
     }
     /// Return only the actions that succeeded
     pub fn all_valid(&self) -> Vec<Action> {
@@ -129,4 +193,14 @@ impl ActionsResults {
             .filter_map(|(action, result)| result.as_ref().ok().map(|_| action.clone()))
             .collect()
     }
+    /// Valid actions worth playing from `state`, best first
+    pub fn ranked(&self, state: &GameState) -> Vec<Hint> {
+        let mut hints: Vec<Hint> = self
+            .data
+            .iter()
+            .filter_map(|(action, result)| Hint::rate(state, action, result.as_ref().ok()?))
+            .collect();
+        hints.sort_by_key(|h| std::cmp::Reverse(h.score));
+        hints
+    }
 }
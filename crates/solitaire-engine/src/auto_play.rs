@@ -0,0 +1,123 @@
+use solitaire_core::prelude::*;
+
+use crate::{action::Action, game_state::GameState, history::MoveRecord};
+
+impl GameState {
+    /// Whether the game is mechanically won: stock and waste empty, every column a face-up
+    /// run going down in rank, and foundations taking one card at a time (not Pyramid pairs)
+    pub fn can_auto_complete(&self) -> bool {
+        self.rules.variant.foundation_move_size() == 1
+            && !self.rules.variant.removes_pairs()
+            && !self.rules.variant.builds_on_waste()
+            && self.stock.is_empty()
+            && self.waste.is_empty()
+            && !self.is_won()
+            && self.columns.values().all(|c| {
+                c.cards().iter().all(|card| *card.face() == Face::Up)
+                    && c.cards()
+                        .iter()
+                        .zip(c.cards().iter().skip(1))
+                        .all(|(below, above)| above.rank() < below.rank())
+            })
+    }
+
+    /// Moves that send every remaining card to the foundations, lowest rank first.
+    ///
+    /// `None` if the state can't be auto-completed.
+    pub fn auto_complete_actions(&self) -> Option<Vec<Action>> {
+        if !self.can_auto_complete() {
+            return None;
+        }
+
+        let mut state = self.clone();
+        let mut actions = vec![];
+        while !state.is_won() {
+            let action = state.lowest_foundation_move()?;
+            if let Action::Move {
+                num_cards,
+                from,
+                to,
+            } = action
+            {
+                state.move_cards(num_cards, from, to).ok()?;
+            }
+            actions.push(action);
+        }
+        Some(actions)
+    }
+
+    /// Move sending the top card of `from` to a foundation, if any accepts it
+    pub fn foundation_move_for(&self, from: PileId) -> Option<Action> {
+        let card = self.pile(from).ok()?.peek(Side::Top)?;
+        let to = self
+            .foundation_ids()
+            .into_iter()
+            .find(|&to| self.accepts(to, card))?;
+        Some(Action::Move {
+            num_cards: 1,
+            from,
+            to,
+        })
+    }
+
+    /// Spider: sends every complete King to Ace run to a foundation, returning the moves made
+    pub fn remove_complete_runs(&mut self) -> Vec<MoveRecord> {
+        let mut records = vec![];
+        while let Some(record) = self
+            .complete_run_move()
+            .and_then(|action| self.apply(&action).ok())
+        {
+            records.push(record);
+        }
+        records
+    }
+
+    /// Plays every waste, cell or column top card that is safe to put on a foundation,
+    /// returning the moves made
+    pub fn auto_play_safe(&mut self) -> Vec<MoveRecord> {
+        let mut records = vec![];
+        loop {
+            let mut sources = vec![PileId::Waste];
+            sources.extend(self.cell_ids());
+            sources.extend(self.column_ids());
+            let next = sources.into_iter().find_map(|from| {
+                let card = self.pile(from).ok()?.peek(Side::Top)?;
+                if *card.face() == Face::Down || !self.is_safe_to_foundation(&card) {
+                    return None;
+                }
+                self.foundation_move_for(from)
+            });
+            let Some(record) = next.and_then(|action| self.apply(&action).ok()) else {
+                break;
+            };
+            records.push(record);
+        }
+        records
+    }
+
+    /// Cell card or column top with the lowest rank that a foundation accepts
+    fn lowest_foundation_move(&self) -> Option<Action> {
+        let mut best: Option<(Rank, Action)> = None;
+        for from in self.cell_ids().into_iter().chain(self.column_ids()) {
+            let Some(card) = self.pile(from).ok().and_then(|p| p.peek(Side::Top)) else {
+                continue;
+            };
+            if best.as_ref().is_some_and(|(rank, _)| rank <= card.rank()) {
+                continue;
+            }
+            if let Some(to) = self
+                .foundation_ids()
+                .into_iter()
+                .find(|&to| self.accepts(to, card))
+            {
+                let action = Action::Move {
+                    num_cards: 1,
+                    from,
+                    to,
+                };
+                best = Some((*card.rank(), action));
+            }
+        }
+        best.map(|(_, action)| action)
+    }
+}
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+use web_time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Source of time for a `Game`, swappable so tests can drive it by hand.
+pub trait Clock: Send + Sync {
+    /// Monotonic time since an arbitrary origin
+    fn now(&self) -> Duration;
+    /// Wall-clock seconds since the Unix epoch
+    fn unix_secs(&self) -> u64;
+}
+
+/// Real time, works natively and on wasm.
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    origin: Instant,
+}
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self {
+            origin: Instant::now(),
+        }
+    }
+}
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+    fn unix_secs(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+}
+
+/// Clock that only moves when told to. Clones share the same time.
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    millis: Arc<AtomicU64>,
+}
+impl ManualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn advance(&self, by: Duration) {
+        self.millis
+            .fetch_add(by.as_millis() as u64, Ordering::Relaxed);
+    }
+    pub fn set(&self, to: Duration) {
+        self.millis.store(to.as_millis() as u64, Ordering::Relaxed);
+    }
+}
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        Duration::from_millis(self.millis.load(Ordering::Relaxed))
+    }
+    fn unix_secs(&self) -> u64 {
+        self.now().as_secs()
+    }
+}
+
+/// Play time of one game.
+///
+/// Only time spent running counts, a loaded game stays paused until resumed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GameClock {
+    /// Wall-clock start of the game, seconds since the Unix epoch
+    pub started_at: u64,
+    /// Play time accumulated before the current run
+    elapsed: Duration,
+    /// Clock reading when the current run began, `None` while paused
+    #[serde(skip)]
+    running_since: Option<Duration>,
+    /// Play time at the moment the game was won
+    pub won_in: Option<Duration>,
+}
+
+impl GameClock {
+    /// Starts counting right away
+    pub fn start(clock: &dyn Clock) -> Self {
+        Self {
+            started_at: clock.unix_secs(),
+            elapsed: Duration::ZERO,
+            running_since: Some(clock.now()),
+            won_in: None,
+        }
+    }
+    pub fn is_running(&self) -> bool {
+        self.running_since.is_some()
+    }
+    pub fn elapsed(&self, clock: &dyn Clock) -> Duration {
+        match self.running_since {
+            Some(since) => self.elapsed + clock.now().saturating_sub(since),
+            None => self.elapsed,
+        }
+    }
+    pub fn pause(&mut self, clock: &dyn Clock) {
+        self.elapsed = self.elapsed(clock);
+        self.running_since = None;
+    }
+    /// Does nothing once the game is won
+    pub fn resume(&mut self, clock: &dyn Clock) {
+        if self.running_since.is_none() && self.won_in.is_none() {
+            self.running_since = Some(clock.now());
+        }
+    }
+    /// Stops the clock and records the winning time
+    pub fn win(&mut self, clock: &dyn Clock) {
+        self.pause(clock);
+        self.won_in = Some(self.elapsed);
+    }
+    /// Takes back a win, the clock stays paused until resumed
+    pub fn unwin(&mut self) {
+        self.won_in = None;
+    }
+}
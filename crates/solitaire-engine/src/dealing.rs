@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+use solitaire_core::prelude::*;
+
+use crate::game_state::GameState;
+
+impl GameState {
+    /// Lays `cards` (in dealing order) out the way `rules.variant` starts
+    pub fn deal(cards: Vec<Card>, rules: RuleSet) -> Self {
+        match rules.variant {
+            Variant::Klondike => deal_klondike(cards, rules),
+            Variant::FreeCell => deal_freecell(cards, rules),
+            Variant::Spider => deal_spider(cards, rules),
+            Variant::Yukon | Variant::Russian => deal_yukon(cards, rules),
+            Variant::Pyramid => deal_pyramid(cards, rules),
+            Variant::Golf => deal_golf(cards, rules),
+            Variant::TriPeaks => deal_tripeaks(cards, rules),
+        }
+    }
+}
+
+/// Empty foundations, pinned to a suit each if the rules say so
+fn foundations(rules: RuleSet) -> HashMap<u8, Foundation> {
+    (0..rules.variant.n_foundations())
+        .zip(FIXED_SUITS.into_iter().cycle())
+        .map(|(id, suit)| {
+            let suit = match rules.foundation_suits {
+                FoundationSuits::Fixed => Some(suit),
+                FoundationSuits::Any => None,
+            };
+            (id, Foundation::new(id, suit, vec![]))
+        })
+        .collect()
+}
+
+fn empty_columns(rules: RuleSet) -> HashMap<u8, Column> {
+    (0..rules.variant.n_columns())
+        .map(|i| (i, Column::with_rules(i, vec![], rules.column_rules())))
+        .collect()
+}
+
+/// Column `i` gets `i + 1` cards, the last one face up, the rest goes to the stock
+fn deal_klondike(mut cards: Vec<Card>, rules: RuleSet) -> GameState {
+    let mut columns = HashMap::new();
+    for i in 0..rules.variant.n_columns() {
+        let num_cards = i as usize + 1; // column 0 gets 1 card, column 1 gets 2, etc.
+
+        // Create column and add cards
+        let mut column = Column::with_rules(i, vec![], rules.column_rules());
+        for (j, card) in cards.drain(..num_cards).enumerate() {
+            let face = if j == 0 { Face::Up } else { Face::Down };
+            column.raw_insert_card(card, Side::Bottom, face);
+        }
+
+        columns.insert(i, column);
+    }
+
+    let stock = Stock::new(cards);
+    let waste = Waste::new(vec![]);
+    GameState::new(columns, stock, waste, foundations(rules), rules)
+}
+
+/// Every card face up, dealt a row at a time across the columns
+fn deal_freecell(cards: Vec<Card>, rules: RuleSet) -> GameState {
+    let n_columns = rules.variant.n_columns();
+    let mut columns = empty_columns(rules);
+    for (i, card) in cards.into_iter().enumerate() {
+        let column = columns
+            .get_mut(&(i as u8 % n_columns))
+            .expect("column was just created");
+        column.raw_insert_card(card, Side::Top, Face::Up);
+    }
+    GameState::new(
+        columns,
+        Stock::new(vec![]),
+        Waste::new(vec![]),
+        foundations(rules),
+        rules,
+    )
+}
+
+/// 54 cards dealt a row at a time, so the first four columns get six, the top card of each
+/// face up. The other 50 stay in the stock to be dealt a row at a time.
+fn deal_spider(mut cards: Vec<Card>, rules: RuleSet) -> GameState {
+    let n_columns = rules.variant.n_columns();
+    let mut columns = empty_columns(rules);
+    let dealt = cards.len().min(54);
+    for (i, card) in cards.drain(..dealt).enumerate() {
+        let column = columns
+            .get_mut(&(i as u8 % n_columns))
+            .expect("column was just created");
+        column.raw_insert_card(card, Side::Top, Face::Down);
+    }
+    for column in columns.values_mut() {
+        column.flip_card_at(0, Side::Top, Face::Up);
+    }
+    GameState::new(
+        columns,
+        Stock::new(cards),
+        Waste::new(vec![]),
+        foundations(rules),
+        rules,
+    )
+}
+
+/// Column `i` gets `i` face-down cards under five face-up ones, column 0 a single face-up
+/// card. No stock: all 52 cards are on the table.
+fn deal_yukon(mut cards: Vec<Card>, rules: RuleSet) -> GameState {
+    let mut columns = empty_columns(rules);
+    for i in 0..rules.variant.n_columns() {
+        let face_down = i as usize;
+        let num_cards = if i == 0 { 1 } else { face_down + 5 };
+        let column = columns.get_mut(&i).expect("column was just created");
+        for (j, card) in cards.drain(..num_cards.min(cards.len())).enumerate() {
+            let face = if j < face_down { Face::Down } else { Face::Up };
+            column.raw_insert_card(card, Side::Top, face);
+        }
+    }
+    GameState::new(
+        columns,
+        Stock::new(vec![]),
+        Waste::new(vec![]),
+        foundations(rules),
+        rules,
+    )
+}
+
+/// The first 28 cards face up into the pyramid, row by row from the top, the rest to the stock
+fn deal_pyramid(mut cards: Vec<Card>, rules: RuleSet) -> GameState {
+    let dealt = cards.len().min(PYRAMID_SIZE as usize);
+    let pyramid: Vec<Card> = cards.drain(..dealt).collect();
+    let mut state = GameState::new(
+        HashMap::new(),
+        Stock::new(cards),
+        Waste::new(vec![]),
+        foundations(rules),
+        rules,
+    );
+    for (i, card) in pyramid.into_iter().enumerate() {
+        if let Some(slot) = state.pyramid.get_mut(&(i as u8)) {
+            slot.raw_insert_card(card, Side::Top, Face::Up);
+        }
+    }
+    state
+}
+
+/// Turns the top card of the stock over to start the waste
+fn start_waste(state: &mut GameState) {
+    if let Ok(card) = state.stock.take_card(Side::Top) {
+        state.waste.raw_insert_card(card, Side::Top, Face::Up);
+    }
+}
+
+/// Five face-up cards a column, dealt a row at a time, and one card turned onto the waste
+fn deal_golf(mut cards: Vec<Card>, rules: RuleSet) -> GameState {
+    let n_columns = rules.variant.n_columns();
+    let mut columns = empty_columns(rules);
+    let dealt = cards.len().min(5 * n_columns as usize);
+    for (i, card) in cards.drain(..dealt).enumerate() {
+        let column = columns
+            .get_mut(&(i as u8 % n_columns))
+            .expect("column was just created");
+        column.raw_insert_card(card, Side::Top, Face::Up);
+    }
+    let mut state = GameState::new(
+        columns,
+        Stock::new(cards),
+        Waste::new(vec![]),
+        foundations(rules),
+        rules,
+    );
+    start_waste(&mut state);
+    state
+}
+
+/// The first 28 cards into the peaks, face up on the bottom row only, and one card turned
+/// onto the waste
+fn deal_tripeaks(mut cards: Vec<Card>, rules: RuleSet) -> GameState {
+    let dealt = cards.len().min(PEAKS_SIZE as usize);
+    let peaks: Vec<Card> = cards.drain(..dealt).collect();
+    let mut state = GameState::new(
+        HashMap::new(),
+        Stock::new(cards),
+        Waste::new(vec![]),
+        foundations(rules),
+        rules,
+    );
+    for (i, card) in peaks.into_iter().enumerate() {
+        if let Some(slot) = state.pyramid.get_mut(&(i as u8)) {
+            slot.raw_insert_card(card, Side::Top, Face::Down);
+        }
+    }
+    state.turn_peaks();
+    start_waste(&mut state);
+    state
+}
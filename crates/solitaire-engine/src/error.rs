@@ -1,20 +1,31 @@
 use std::fmt;
 
 use serde::{Deserialize, Serialize};
-use solitaire_core::{error::PileError, pile::PileId};
+use solitaire_core::{card::Card, error::PileError, pile::PileId};
+
+use crate::action::Action;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GameError {
-    NoCardToDraw,     // Tried to draw from empty stock
-    NothingToRecycle, // Tried to recycle when waste is empty
-    StockNotEmpty,    // Tried to recycle when stock is not empty
-    InvalidMove,      // Move not allowed by game rules
-    UndoUnavailable,  // Tried to undo but no history
-    FoundationFull,   // Foundation pile cannot accept more cards
+    NoCardToDraw,      // Tried to draw from empty stock
+    NothingToRecycle,  // Tried to recycle when waste is empty
+    StockNotEmpty,     // Tried to recycle when stock is not empty
+    PassLimitReached,  // Tried to recycle after the last allowed pass
+    EmptyColumnOnDeal, // Tried to deal a Spider row with a column empty
+    InvalidMove,       // Move not allowed by game rules
+    UndoUnavailable,   // Tried to undo but no history
+    RedoUnavailable,   // Tried to redo but nothing was undone
+    FoundationFull,    // Foundation pile cannot accept more cards
     ColumnNotExist(u8),
     FoundationNotExist(u8),
+    CellNotExist(u8),
+    SlotNotExist(u8),
+    CardUnavailable(PileId), // Pyramid card still covered, or an empty pile
+    NotAPair,                // Pyramid cards that do not add up to 13
+    NotAdjacent,             // Golf or TriPeaks card not one rank off the waste
     PileError(PileError),
     InvalidPile(PileId),
+    InvalidSeed(u64), // Seed outside the deal algorithm's seeds
 }
 
 impl std::error::Error for GameError {}
@@ -25,13 +36,154 @@ impl fmt::Display for GameError {
             GameError::NoCardToDraw => write!(f, "Cannot draw: stock is empty"),
             GameError::NothingToRecycle => write!(f, "Cannot recycle: waste is empty"),
             GameError::StockNotEmpty => write!(f, "Cannot recycle: stock is not empty"),
+            GameError::PassLimitReached => write!(f, "Cannot recycle: no passes left"),
+            GameError::EmptyColumnOnDeal => write!(f, "Cannot deal: a column is empty"),
             GameError::InvalidMove => write!(f, "Invalid move according to the rules"),
             GameError::UndoUnavailable => write!(f, "Nothing to undo"),
+            GameError::RedoUnavailable => write!(f, "Nothing to redo"),
             GameError::FoundationFull => write!(f, "Foundation pile is full"),
             GameError::ColumnNotExist(i) => write!(f, "Column {} doesn not exist", i),
             GameError::FoundationNotExist(i) => write!(f, "Foundation {} doesn not exist", i),
+            GameError::CellNotExist(i) => write!(f, "Cell {} does not exist", i),
+            GameError::SlotNotExist(i) => write!(f, "Pyramid slot {} does not exist", i),
+            GameError::CardUnavailable(id) => write!(f, "No card of {id} can be played"),
+            GameError::NotAPair => write!(f, "Cards must add up to 13, or be a King"),
+            GameError::NotAdjacent => {
+                write!(
+                    f,
+                    "Card must be one rank above or below the top of the waste"
+                )
+            }
             GameError::PileError(e) => write!(f, "{e:?}"),
             GameError::InvalidPile(id) => write!(f, "Pile {id} is not valid"),
+            GameError::InvalidSeed(seed) => write!(f, "Seed {seed} is not a deal number"),
+        }
+    }
+}
+
+/// Why a replay could not be read or played back
+#[derive(Debug, Clone)]
+pub enum ReplayError {
+    UnsupportedVersion(u32),
+    Json(String),
+    /// Malformed text replay, `line` starts at 1
+    Parse {
+        line: usize,
+        message: String,
+    },
+    /// The seed is not one of the deal algorithm's seeds
+    InvalidSeed(u64),
+    /// The starting position breaks the rules of the game
+    InvalidPosition(StateError),
+    /// Action number `index` (from 0) is not legal at that point of the game
+    IllegalAction {
+        index: usize,
+        action: Action,
+        error: GameError,
+    },
+}
+
+impl std::error::Error for ReplayError {}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::UnsupportedVersion(v) => write!(f, "Unsupported replay version {v}"),
+            ReplayError::Json(e) => write!(f, "Invalid replay JSON: {e}"),
+            ReplayError::Parse { line, message } => write!(f, "Line {line}: {message}"),
+            ReplayError::InvalidSeed(seed) => write!(f, "Seed {seed} is not a deal number"),
+            ReplayError::InvalidPosition(e) => write!(f, "Invalid starting position: {e}"),
+            ReplayError::IllegalAction {
+                index,
+                action,
+                error,
+            } => write!(f, "Action {index} ({action}) is illegal: {error}"),
+        }
+    }
+}
+
+/// One way a position differs from any position reachable in a dealt game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    MissingCard(Card),
+    DuplicateCard {
+        card: Card,
+        count: usize,
+    },
+    /// Pile that the variant does not have, see `PileId::is_valid_in`
+    InvalidPile(PileId),
+    /// Pile that the variant has but the position lacks
+    MissingPile(PileId),
+    /// Pile stored under `key` calls itself `found`
+    MismatchedPile {
+        key: PileId,
+        found: PileId,
+    },
+    /// Card `index` (from the bottom) does not follow the cards beneath it
+    FoundationOutOfOrder {
+        foundation: u8,
+        index: usize,
+        card: Card,
+    },
+    /// Foundation cards are always face up
+    FaceDownOnFoundation {
+        foundation: u8,
+        index: usize,
+    },
+    /// Face-down card `index` (from the bottom) has no face-up card above it
+    ExposedFaceDown {
+        column: u8,
+        index: usize,
+    },
+    /// Pyramid slot emptied while cards still overlap it
+    CoveredGap(u8),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::MissingCard(card) => write!(f, "{card} is missing"),
+            Violation::CoveredGap(i) => write!(f, "pyramid slot {i} is empty under other cards"),
+            Violation::DuplicateCard { card, count } => write!(f, "{card} appears {count} times"),
+            Violation::InvalidPile(id) => write!(f, "{id} does not exist in this game"),
+            Violation::MissingPile(id) => write!(f, "{id} is missing"),
+            Violation::MismatchedPile { key, found } => write!(f, "{key} holds {found}"),
+            Violation::FoundationOutOfOrder {
+                foundation,
+                index,
+                card,
+            } => write!(
+                f,
+                "{card} at position {index} of foundation {foundation} is out of sequence"
+            ),
+            Violation::FaceDownOnFoundation { foundation, index } => write!(
+                f,
+                "card at position {index} of foundation {foundation} is face down"
+            ),
+            Violation::ExposedFaceDown { column, index } => write!(
+                f,
+                "face-down card at position {index} of column {column} is not covered by a face-up card"
+            ),
+        }
+    }
+}
+
+/// Every violation found in a position, see `Game::from_state`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateError {
+    pub violations: Vec<Violation>,
+}
+
+impl std::error::Error for StateError {}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, violation) in self.violations.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{violation}")?;
         }
+        Ok(())
     }
 }
@@ -1,153 +1,284 @@
-use getset::Getters;
-use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+use getset::{CopyGetters, Getters, Setters};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use solitaire_core::prelude::*;
-use std::collections::HashMap;
+use std::{sync::Arc, time::Duration};
 
 use crate::{
     action::Action,
-    error::GameError,
+    clock::{Clock, GameClock, SystemClock},
+    error::{GameError, StateError},
+    hint::Hint,
+    history::HistoryEntry,
     prelude::{ActionsResults, GameState},
+    replay::TimedAction,
+    score::{Score, ScoringMode},
 };
 
-#[derive(Clone, Serialize, Deserialize, Getters)]
+#[derive(Clone, Serialize, Deserialize, Getters, CopyGetters, Setters)]
 pub struct Game {
     pub state: GameState,
-    state_history: Vec<GameState>,
+    #[serde(default)]
+    history: Vec<HistoryEntry>,
+    /// Entries undone since the last new action, most recent last
+    #[serde(default)]
+    redo_stack: Vec<HistoryEntry>,
+    #[getset(get_copy = "pub")]
     seed: u64,
+    /// How `seed` was turned into the deal
+    #[serde(default)]
+    #[getset(get_copy = "pub")]
+    deal: DealAlgorithm,
     #[getset(get = "pub")]
     actions_results: ActionsResults,
+    /// Whether safe cards go to the foundations on their own after each action
+    #[serde(default)]
+    #[getset(get_copy = "pub", set = "pub")]
+    auto_play: bool,
+    #[serde(default)]
+    #[getset(get_copy = "pub")]
+    score: Score,
+    #[serde(default)]
+    #[getset(get = "pub")]
+    clock: GameClock,
+    /// Every action played since the deal, for replays
+    #[serde(default)]
+    #[getset(get = "pub")]
+    log: Vec<TimedAction>,
+    /// Position the game started from when it was not dealt from `seed`
+    #[serde(default)]
+    #[getset(get = "pub")]
+    start_position: Option<GameState>,
+    /// Where `clock` reads the time from
+    #[serde(skip, default = "default_clock_source")]
+    clock_source: Arc<dyn Clock>,
+}
+
+fn default_clock_source() -> Arc<dyn Clock> {
+    Arc::new(SystemClock::default())
 }
 
 impl Game {
+    /// Creates a new game with the default rules
     pub fn new(seed: Option<u64>) -> Self {
-        // Generate a seed if none provided
+        Self::with_rules(seed, RuleSet::default())
+    }
+    /// Creates a new game played with the given rules
+    pub fn with_rules(seed: Option<u64>, rules: RuleSet) -> Self {
+        Self::with_deal(seed, rules, DealAlgorithm::Portable).expect("portable deals take any seed")
+    }
+    /// Creates a new game whose deck is shuffled by `deal`.
+    /// Fails if `seed` is not one of `deal.seeds()`.
+    pub fn with_deal(
+        seed: Option<u64>,
+        rules: RuleSet,
+        deal: DealAlgorithm,
+    ) -> Result<Self, GameError> {
+        // Generate a seed if none provided, one that `deal` takes as it is
         let seed = seed.unwrap_or_else(|| {
             let mut rng = rand::thread_rng();
-            rng.r#gen()
+            rng.gen_range(deal.seeds())
         });
 
-        let mut cards = all_cards();
-
-        // Shuffle using the seed
-        let mut rng = StdRng::seed_from_u64(seed);
-        cards.shuffle(&mut rng);
-
-        // Columns
-        let mut columns = HashMap::new();
-        let column_ids = [0, 1, 2, 3, 4, 5, 6];
-        for i in column_ids {
-            let num_cards = i + 1; // column 0 gets 1 card, column 1 gets 2, etc.
-
-            // Remove the first num_cards from deck
-            let mut cards_for_col = Vec::with_capacity(num_cards);
-            for _ in 0..num_cards {
-                cards_for_col.push(cards.remove(0));
-            }
-
-            // Create column and add cards
-            let mut column = Column::new(i as u8, vec![]);
-            for (j, card) in cards_for_col.iter().enumerate() {
-                let face = if j == 0 { Face::Up } else { Face::Down };
-                column.raw_insert_card(*card, Side::Bottom, face);
-            }
-
-            columns.insert(i as u8, column);
+        let cards = match rules.variant {
+            Variant::Spider => deal.shuffle_deck(seed, rules.deck()),
+            _ => deal.shuffle(seed),
         }
-
-        // Stock
-        let cards_for_stock = cards.to_vec();
-        let stock = Stock::new(cards_for_stock);
-
-        // Waste
-        let waste = Waste::new(vec![]);
-
-        // Foundations
-        let mut foundations = HashMap::new();
-        let foundation_ids = [0, 1, 2, 3];
-        let foundation_suits = [Suit::Heart, Suit::Spade, Suit::Club, Suit::Diamond];
-        for (id, suit) in foundation_ids.into_iter().zip(foundation_suits.into_iter()) {
-            foundations.insert(id, Foundation::new(id, suit, vec![]));
+        .ok_or(GameError::InvalidSeed(seed))?;
+        let initial_state = GameState::deal(cards, rules);
+        Ok(Self::from_parts(seed, deal, initial_state, None))
+    }
+    /// Creates a game starting from an arbitrary position, played with `rules`.
+    /// Fails with every violation found if the position could not come from a deal.
+    pub fn from_state(mut state: GameState, rules: RuleSet) -> Result<Self, StateError> {
+        state.set_rules(rules);
+        let violations = state.violations();
+        if !violations.is_empty() {
+            return Err(StateError { violations });
         }
-
-        let initial_state = GameState::new(columns, stock, waste, foundations);
-
+        Ok(Self::from_parts(
+            0,
+            DealAlgorithm::default(),
+            state.clone(),
+            Some(state),
+        ))
+    }
+    fn from_parts(
+        seed: u64,
+        deal: DealAlgorithm,
+        state: GameState,
+        start_position: Option<GameState>,
+    ) -> Self {
+        let clock_source = default_clock_source();
         let mut game = Self {
             seed,
-            state: initial_state,
-            state_history: vec![],
+            deal,
+            state,
+            history: vec![],
+            redo_stack: vec![],
             actions_results: ActionsResults::empty(),
+            auto_play: false,
+            score: Score::default(),
+            clock: GameClock::start(clock_source.as_ref()),
+            log: vec![],
+            start_position,
+            clock_source,
         };
-        let ar = ActionsResults::from_game(&game);
-        game.actions_results = ar;
+        game.actions_results = ActionsResults::from_state(&game.state);
         game
     }
-    pub fn reset(&mut self) {
-        *self = Game::new(Some(self.seed));
+    /// Starts the game over from its deal or position. Fails, leaving the game as it
+    /// is, if a loaded game carries a seed its deal algorithm does not take
+    pub fn reset(&mut self) -> Result<(), GameError> {
+        let auto_play = self.auto_play;
+        let score = self.score.restarted();
+        let clock_source = self.clock_source.clone();
+        *self = match &self.start_position {
+            // Validated when the game was created
+            Some(start) => Game::from_parts(
+                0,
+                DealAlgorithm::default(),
+                start.clone(),
+                Some(start.clone()),
+            ),
+            None => Game::with_deal(Some(self.seed), self.state.rules, self.deal)?,
+        };
+        self.auto_play = auto_play;
+        self.score = score;
+        self.set_clock_source(clock_source);
+        Ok(())
+    }
+    /// Reads time from `source` from now on, restarting the game clock
+    pub fn set_clock_source(&mut self, source: Arc<dyn Clock>) {
+        self.clock = GameClock::start(source.as_ref());
+        self.clock_source = source;
+    }
+    /// Play time so far, or the winning time once won
+    pub fn elapsed(&self) -> Duration {
+        self.clock.elapsed(self.clock_source.as_ref())
     }
+    /// Stops counting play time, e.g. while the window is unfocused
+    pub fn pause(&mut self) {
+        self.clock.pause(self.clock_source.as_ref());
+    }
+    pub fn resume(&mut self) {
+        self.clock.resume(self.clock_source.as_ref());
+    }
+    /// Score including time penalties so far (and the time bonus once won)
+    pub fn timed_score(&self) -> Score {
+        if self.clock.won_in.is_some() {
+            self.score
+        } else {
+            self.score.with_time(self.elapsed().as_secs())
+        }
+    }
+    /// Restarts scoring with `mode`, `bankroll` is carried in for cumulative Vegas
+    pub fn set_scoring(&mut self, mode: ScoringMode, bankroll: i32) {
+        self.score = Score::new(mode, bankroll);
+    }
+    /// Moves leading to the current state: undo takes one back, redo replays it
     pub fn n_moves(&self) -> usize {
-        self.state_history.len()
+        self.history.len()
+    }
+    pub fn can_undo(&self) -> bool {
+        !self.history.is_empty()
+    }
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+    /// Best action to play next, if any is worth playing
+    pub fn hint(&self) -> Option<Hint> {
+        self.actions_results.ranked(&self.state).into_iter().next()
     }
 }
 
 impl Game {
     /// Simulate an action on a clone of the current state, returns the result
     pub fn test_action(&self, action: Action) -> Result<GameState, GameError> {
-        let mut state_clone = self.state.clone();
-
         match action {
-            Action::Draw => state_clone.draw()?,
-            Action::Recycle => state_clone.recycle()?,
-            Action::Move {
-                num_cards,
-                from,
-                to,
-            } => state_clone.move_cards(num_cards, from, to)?,
-            Action::Undo => return Err(GameError::UndoUnavailable), // cannot simulate undo
-            _ => return Err(GameError::InvalidMove),
+            Action::Undo => Err(GameError::UndoUnavailable), // cannot simulate undo
+            Action::Redo => Err(GameError::RedoUnavailable), // cannot simulate redo
+            _ => self.state.test_action(&action),
         }
-
-        Ok(state_clone)
     }
+    /// Plays `action`, logging it when it succeeds
     pub fn handle_action(&mut self, action: Action) -> Result<String, GameError> {
+        let at_ms = self.elapsed().as_millis() as u64;
+        let message = self.play(action.clone())?;
+        self.log.push(TimedAction { at_ms, action });
+        Ok(message)
+    }
+    fn play(&mut self, action: Action) -> Result<String, GameError> {
         if let Action::Undo = action {
-            if let Some(prev) = self.state_history.pop() {
-                self.state = prev;
-                return Ok("Undid last action".to_string());
-            } else {
-                return Err(GameError::UndoUnavailable);
+            let entry = self.history.pop().ok_or(GameError::UndoUnavailable)?;
+            for record in entry.records.iter().rev() {
+                self.state.revert(record)?;
+            }
+            self.score.points = entry.score_before;
+            self.score.streak = entry.streak_before;
+            self.redo_stack.push(entry);
+            if self.clock.won_in.is_some() && !self.state.is_won() {
+                self.clock.unwin();
+                self.resume();
             }
+            self.actions_results = ActionsResults::from_state(&self.state);
+            return Ok(action.to_string());
+        }
+        if let Action::Redo = action {
+            let entry = self.redo_stack.pop().ok_or(GameError::RedoUnavailable)?;
+            for record in &entry.records {
+                self.state.apply(&record.action())?;
+                self.score.add(record, &self.state.rules);
+            }
+            self.check_win();
+            self.history.push(entry);
+            self.actions_results = ActionsResults::from_state(&self.state);
+            return Ok(action.to_string());
         }
 
-        // TEMP store current state before attempting action
-        let temp_state = self.state.clone();
+        // Try to apply action, a failed one leaves state and history untouched
+        let record = self.state.apply(&action)?;
 
-        // Try to apply action
-        let result = match action {
-            Action::Draw => self.state.draw(),
-            Action::Recycle => self.state.recycle(),
-            Action::Move {
-                num_cards,
-                from,
-                to,
-            } => self.state.move_cards(num_cards, from, to),
-            _ => return Err(GameError::InvalidMove),
-        };
+        // Follow-up moves share the history entry, so one undo reverts them all
+        let mut records = vec![record];
+        records.extend(self.state.remove_complete_runs());
+        if self.auto_play {
+            records.extend(self.state.auto_play_safe());
+        }
+        let n_auto = records.len() - 1;
 
-        match result {
-            Ok(_) => {
-                // Action succeeded → push previous state
-                self.state_history.push(temp_state);
+        let score_before = self.score.points;
+        let streak_before = self.score.streak;
+        for record in &records {
+            self.score.add(record, &self.state.rules);
+        }
+        self.check_win();
 
-                // Update cached results
-                self.actions_results = ActionsResults::from_game(self);
+        // Undone entries are out of reach now
+        self.history.push(HistoryEntry {
+            records,
+            score_before,
+            streak_before,
+        });
+        self.redo_stack.clear();
 
-                Ok(action.to_string())
-            }
-            Err(e) => {
-                // Action failed → do not modify history
-                Err(e)
-            }
+        // Update cached results
+        self.actions_results = ActionsResults::from_state(&self.state);
+
+        if n_auto == 0 {
+            Ok(action.to_string())
+        } else {
+            Ok(format!("{action} (+{n_auto} auto)"))
+        }
+    }
+    /// Stops the clock and settles time scoring when the game was just won
+    fn check_win(&mut self) {
+        if !self.state.is_won() || self.clock.won_in.is_some() {
+            return;
         }
+        let secs = self.elapsed().as_secs();
+        self.clock.win(self.clock_source.as_ref());
+        self.score = self.score.with_time(secs);
+        self.score.points += self.score.time_bonus(secs);
     }
 }
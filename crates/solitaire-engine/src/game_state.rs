@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 use solitaire_core::prelude::*;
 
-use crate::error::GameError;
+use crate::{action::Action, error::GameError};
 
 #[derive(Clone, Serialize, Deserialize, Getters)]
 pub struct GameState {
@@ -12,20 +12,76 @@ pub struct GameState {
     pub waste: Waste,
     pub columns: HashMap<u8, Column>,
     pub foundations: HashMap<u8, Foundation>,
+    /// FreeCell cells, none in other variants
+    #[serde(default)]
+    pub cells: HashMap<u8, FreeCell>,
+    /// Pyramid slots, none in other variants
+    #[serde(default)]
+    pub pyramid: HashMap<u8, PyramidSlot>,
+    /// Rules the game is played with
+    #[serde(default)]
+    pub rules: RuleSet,
+    /// Number of times the waste has been recycled into the stock
+    #[serde(default)]
+    pub recycles: u32,
 }
 
 impl GameState {
+    /// Position with the given piles, the variant's other piles starting out empty
     pub fn new(
-        columns: HashMap<u8, Column>,
+        mut columns: HashMap<u8, Column>,
         stock: Stock,
         waste: Waste,
-        foundations: HashMap<u8, Foundation>,
+        mut foundations: HashMap<u8, Foundation>,
+        rules: RuleSet,
     ) -> Self {
-        Self {
+        for i in 0..rules.variant.n_columns() {
+            columns.entry(i).or_insert_with(|| Column::new(i, vec![]));
+        }
+        for i in 0..rules.variant.n_foundations() {
+            foundations
+                .entry(i)
+                .or_insert_with(|| Foundation::new(i, None, vec![]));
+        }
+        let cells = (0..rules.variant.n_cells())
+            .map(|i| (i, FreeCell::new(i)))
+            .collect();
+        let pyramid = (0..rules.variant.n_pyramid_slots())
+            .map(|i| (i, PyramidSlot::new(i)))
+            .collect();
+        let mut state = Self {
             stock,
             waste,
             columns,
             foundations,
+            cells,
+            pyramid,
+            rules,
+            recycles: 0,
+        };
+        state.set_rules(rules);
+        state
+    }
+
+    /// Changes the rules, passing them on to the piles that enforce them
+    pub fn set_rules(&mut self, rules: RuleSet) {
+        self.rules = rules;
+        for column in self.columns.values_mut() {
+            column.set_rules(rules.column_rules());
+        }
+        // Pyramid discards whatever pairs come up
+        if !rules.variant.removes_pairs() {
+            for foundation in self.foundations.values_mut() {
+                foundation.set_suits(rules.foundation_suits);
+            }
+        }
+    }
+
+    /// Number of recycles still allowed, `None` if unlimited
+    pub fn remaining_passes(&self) -> Option<u32> {
+        match self.rules.pass_limit {
+            PassLimit::Unlimited => None,
+            PassLimit::Passes(n) => Some(n.saturating_sub(1).saturating_sub(self.recycles)),
         }
     }
 
@@ -45,6 +101,18 @@ impl GameState {
         ids
     }
 
+    pub fn cell_ids(&self) -> Vec<PileId> {
+        let mut ids: Vec<PileId> = self.cells.keys().map(|id| PileId::Cell(*id)).collect();
+        ids.sort();
+        ids
+    }
+
+    pub fn pyramid_ids(&self) -> Vec<PileId> {
+        let mut ids: Vec<PileId> = self.pyramid.keys().map(|id| PileId::Pyramid(*id)).collect();
+        ids.sort();
+        ids
+    }
+
     pub fn pile(&self, id: PileId) -> Result<&dyn PileBehavior, GameError> {
         match id {
             PileId::Stock => Ok(&self.stock),
@@ -59,6 +127,16 @@ impl GameState {
                 .get(&n)
                 .map(|f| f as &dyn PileBehavior)
                 .ok_or(GameError::FoundationNotExist(n)),
+            PileId::Cell(n) => self
+                .cells
+                .get(&n)
+                .map(|c| c as &dyn PileBehavior)
+                .ok_or(GameError::CellNotExist(n)),
+            PileId::Pyramid(n) => self
+                .pyramid
+                .get(&n)
+                .map(|s| s as &dyn PileBehavior)
+                .ok_or(GameError::SlotNotExist(n)),
         }
     }
 
@@ -76,29 +154,72 @@ impl GameState {
                 .get_mut(&n)
                 .map(|f| f as &mut dyn PileBehavior)
                 .ok_or(GameError::FoundationNotExist(n)),
+            PileId::Cell(n) => self
+                .cells
+                .get_mut(&n)
+                .map(|c| c as &mut dyn PileBehavior)
+                .ok_or(GameError::CellNotExist(n)),
+            PileId::Pyramid(n) => self
+                .pyramid
+                .get_mut(&n)
+                .map(|s| s as &mut dyn PileBehavior)
+                .ok_or(GameError::SlotNotExist(n)),
         }
     }
 }
 
 impl GameState {
+    /// Moves up to `rules.draw_count` cards from stock to waste, fanning them out.
+    /// Without a waste (Spider) the stock is dealt onto the columns instead.
     pub fn draw(&mut self) -> Result<(), GameError> {
+        if !self.rules.variant.has_waste() {
+            return self.deal_row();
+        }
         let from_id = PileId::Stock;
         let to_id = PileId::Waste;
 
-        // Take card from stock
-        let card = {
-            let from = self.pile_mut(from_id)?;
-            let mut cards = from
-                .take_cards(1, Side::Top)
-                .map_err(|_| GameError::NoCardToDraw)?;
-            cards.pop().unwrap()
-        };
+        if self.pile(from_id)?.is_empty() {
+            return Err(GameError::NoCardToDraw);
+        }
+        let n = self.rules.draw_count.max(1).min(self.pile(from_id)?.len());
 
-        // Place card into waste
-        let to = self.pile_mut(to_id)?;
-        to.insert_cards(vec![card], Side::Top, Face::Up)
-            .map_err(GameError::PileError)?;
+        // Previous fan is squared up under the new one
+        self.waste.reset_fan();
+
+        for _ in 0..n {
+            // Take card from stock
+            let card = {
+                let from = self.pile_mut(from_id)?;
+                let mut cards = from
+                    .take_cards(1, Side::Top)
+                    .map_err(|_| GameError::NoCardToDraw)?;
+                cards.pop().unwrap()
+            };
+
+            // Place card into waste
+            let to = self.pile_mut(to_id)?;
+            to.insert_cards(vec![card], Side::Top, Face::Up)
+                .map_err(GameError::PileError)?;
+        }
+
+        Ok(())
+    }
 
+    /// Spider: one card from the stock face up onto each column, none may be empty
+    pub fn deal_row(&mut self) -> Result<(), GameError> {
+        if self.stock.is_empty() {
+            return Err(GameError::NoCardToDraw);
+        }
+        if self.columns.values().any(|c| c.is_empty()) {
+            return Err(GameError::EmptyColumnOnDeal);
+        }
+        for id in self.column_ids() {
+            let Ok(card) = self.stock.take_card(Side::Top) else {
+                break;
+            };
+            self.pile_mut(id)?
+                .raw_insert_card(card, Side::Top, Face::Up);
+        }
         Ok(())
     }
 
@@ -112,6 +233,9 @@ impl GameState {
         if !self.pile(to_id)?.is_empty() {
             return Err(GameError::StockNotEmpty);
         }
+        if self.remaining_passes() == Some(0) {
+            return Err(GameError::PassLimitReached);
+        }
 
         // Take all cards from waste
         let cards = {
@@ -125,6 +249,7 @@ impl GameState {
             to.insert_cards(cards.clone(), Side::Bottom, Face::Down)
                 .map_err(GameError::PileError)?;
         }
+        self.recycles += 1;
 
         Ok(())
     }
@@ -135,16 +260,55 @@ impl GameState {
         from: PileId,
         to: PileId,
     ) -> Result<(), GameError> {
-        if from == to {
+        if self.rules.variant.builds_on_waste() {
+            return self.play_to_waste(num_cards, from, to);
+        }
+        // Pyramid cards only leave in pairs
+        if from == to || self.rules.variant.removes_pairs() {
             return Err(GameError::InvalidMove);
         }
         match (from, to) {
             (PileId::Stock, _) | (_, PileId::Waste) | (_, PileId::Stock) => {
                 return Err(GameError::InvalidMove);
             }
+            (PileId::Foundation(_), PileId::Column(_) | PileId::Cell(_))
+                if !self.rules.foundation_to_tableau =>
+            {
+                return Err(GameError::InvalidMove);
+            }
+            (_, PileId::Foundation(_))
+                if num_cards != self.rules.variant.foundation_move_size() =>
+            {
+                return Err(GameError::InvalidMove);
+            }
+            (_, PileId::Cell(_)) if num_cards > 1 => {
+                return Err(GameError::InvalidMove);
+            }
+            (PileId::Column(_), PileId::Column(_)) if self.rules.variant.n_cells() > 0 => {
+                let max = self.supermove_capacity(to);
+                if num_cards > max {
+                    return Err(GameError::PileError(PileError::InvalidTakeTooMany {
+                        pile: from,
+                        requested: num_cards,
+                        max,
+                    }));
+                }
+            }
             (_, _) => {}
         }
 
+        // A group has to be in sequence already, FreeCell deals every card face up
+        if let PileId::Column(i) = from
+            && num_cards > 1
+            && !self.rules.variant.moves_unordered_groups()
+            && !self
+                .columns
+                .get(&i)
+                .is_some_and(|c| c.is_sequence(num_cards))
+        {
+            return Err(GameError::InvalidMove);
+        }
+
         // --- Save top card face of 'from' pile ---
         let old_top_face = {
             let from_pile = self.pile_mut(from)?;
@@ -163,9 +327,29 @@ impl GameState {
 
         // --- Try inserting into 'to' pile ---
         {
+            // Foundations build up from the Ace, so a Spider run goes on top card first
+            let ordered = match to {
+                PileId::Foundation(_) => cards.iter().rev().copied().collect(),
+                _ => cards.clone(),
+            };
+            let unordered = self.rules.variant.moves_unordered_groups();
             let dest_pile = self.pile_mut(to)?;
-            if let Err(e) = dest_pile.insert_cards(cards.clone(), Side::Top, Face::Up) {
-                // Rollback: put cards back
+            let dest_len = dest_pile.len();
+            let inserted = match (to, ordered.split_first()) {
+                // Only the bottom card of a Yukon group has to fit, the rest ride along
+                (PileId::Column(_), Some((bottom, rest))) if unordered => dest_pile
+                    .insert_card(*bottom, Side::Top, Face::Up)
+                    .map(|()| {
+                        for card in rest {
+                            dest_pile.raw_insert_card(*card, Side::Top, Face::Up);
+                        }
+                    }),
+                _ => dest_pile.insert_cards(ordered, Side::Top, Face::Up),
+            };
+            if let Err(e) = inserted {
+                // Rollback: drop the cards that did go in, then put them all back
+                let placed = dest_pile.len() - dest_len;
+                dest_pile.raw_take_cards(placed, Side::Top);
                 let from_pile = self.pile_mut(from)?;
                 for card in cards {
                     from_pile.raw_insert_card(card, Side::Top, Face::Up);
@@ -196,7 +380,198 @@ impl GameState {
         Ok(())
     }
 
+    /// Cards that can move together onto `to` by way of free cells and empty columns:
+    /// (free cells + 1) · 2^(empty columns other than `to`)
+    pub fn supermove_capacity(&self, to: PileId) -> usize {
+        let free_cells = self.cells.values().filter(|c| c.is_empty()).count();
+        let empty_columns = self
+            .columns
+            .values()
+            .filter(|c| c.is_empty() && c.id() != to)
+            .count();
+        (free_cells + 1) << empty_columns
+    }
+
+    /// Whether pile `to` would accept `card` on top, checked on a copy of that pile only
+    pub fn accepts(&self, to: PileId, card: Card) -> bool {
+        match to {
+            PileId::Column(i) => self
+                .columns
+                .get(&i)
+                .is_some_and(|c| c.clone().insert_card(card, Side::Top, Face::Up).is_ok()),
+            PileId::Foundation(i) => self
+                .foundations
+                .get(&i)
+                .is_some_and(|f| f.clone().insert_card(card, Side::Top, Face::Up).is_ok()),
+            PileId::Cell(i) => self.cells.get(&i).is_some_and(|c| c.is_empty()),
+            _ => false,
+        }
+    }
+
+    /// Highest rank of `suit` already placed on a foundation
+    pub fn foundation_rank(&self, suit: Suit) -> Option<Rank> {
+        self.foundations
+            .values()
+            .filter(|f| f.suit() == Some(suit))
+            .filter_map(|f| f.peek(Side::Top))
+            .map(|c| *c.rank())
+            .max()
+    }
+
+    /// Whether `card` can go to a foundation without ever being needed on the tableau again
+    pub fn is_safe_to_foundation(&self, card: &Card) -> bool {
+        let rank = *card.rank() as u8;
+        if rank <= 2 {
+            return true;
+        }
+        // Every card that could be built on it must already be on a foundation
+        let played = |suit: &Suit| self.foundation_rank(*suit).map_or(0, |r| r as u8) >= rank - 1;
+        let suits = [Suit::Heart, Suit::Club, Suit::Diamond, Suit::Spade];
+        match self.rules.build_order {
+            BuildOrder::AlternateColor => suits
+                .iter()
+                .filter(|s| s.color() != card.color())
+                .all(played),
+            BuildOrder::SameSuit => true,
+            BuildOrder::AnySuit => suits.iter().filter(|s| *s != card.suit()).all(played),
+        }
+    }
+
+    /// Whether pyramid slot `i` holds a card that no other card overlaps
+    pub fn is_uncovered(&self, i: u8) -> bool {
+        self.pyramid.get(&i).is_some_and(|s| !s.is_empty())
+            && self
+                .rules
+                .variant
+                .covering_slots(i)
+                .iter()
+                .all(|j| self.pyramid.get(j).is_none_or(|s| s.is_empty()))
+    }
+
+    /// Card of `id` that can be paired off: an uncovered pyramid card or the top of the waste
+    pub fn available_card(&self, id: PileId) -> Option<Card> {
+        match id {
+            PileId::Pyramid(i) if !self.is_uncovered(i) => None,
+            PileId::Pyramid(_) | PileId::Waste => self.pile(id).ok()?.peek(Side::Top),
+            _ => None,
+        }
+    }
+
+    /// Piles whose top card can be paired off right now
+    pub fn available_piles(&self) -> Vec<PileId> {
+        let mut piles = self.pyramid_ids();
+        piles.push(PileId::Waste);
+        piles.retain(|id| self.available_card(*id).is_some());
+        piles
+    }
+
+    /// Pyramid: discards the cards of `first` and `second` (a King alone without it) onto
+    /// the foundation
+    pub fn remove_pair(&mut self, first: PileId, second: Option<PileId>) -> Result<(), GameError> {
+        if !self.rules.variant.removes_pairs() || Some(first) == second {
+            return Err(GameError::InvalidMove);
+        }
+        let card = |id| {
+            self.available_card(id)
+                .ok_or(GameError::CardUnavailable(id))
+        };
+        let first_card = card(first)?;
+        let second_card = second.map(card).transpose()?;
+        if !is_pair(&first_card, second_card.as_ref()) {
+            return Err(GameError::NotAPair);
+        }
+        for id in [Some(first), second].into_iter().flatten() {
+            let card = self
+                .pile_mut(id)?
+                .take_card(Side::Top)
+                .map_err(GameError::PileError)?;
+            self.discard_mut()?
+                .raw_insert_card(card, Side::Top, Face::Up);
+        }
+        Ok(())
+    }
+    /// Golf and TriPeaks: plays the top card of a column or a free peak card onto the waste,
+    /// one rank above or below its top card
+    fn play_to_waste(
+        &mut self,
+        num_cards: usize,
+        from: PileId,
+        to: PileId,
+    ) -> Result<(), GameError> {
+        let card = match from {
+            PileId::Column(_) => self.pile(from)?.peek(Side::Top),
+            PileId::Pyramid(i) if self.is_uncovered(i) => self.pile(from)?.peek(Side::Top),
+            _ => None,
+        };
+        let (Some(card), PileId::Waste, 1) = (card, to, num_cards) else {
+            return Err(GameError::InvalidMove);
+        };
+        if let Some(top) = self.waste.peek(Side::Top)
+            && !card.rank().is_adjacent(top.rank(), self.rules.wrap)
+        {
+            return Err(GameError::NotAdjacent);
+        }
+        let card = self
+            .pile_mut(from)?
+            .take_card(Side::Top)
+            .map_err(GameError::PileError)?;
+        self.waste.raw_insert_card(card, Side::Top, Face::Up);
+        self.turn_peaks();
+        Ok(())
+    }
+    /// TriPeaks: turns peak cards face up once uncovered, and back down while covered
+    pub(crate) fn turn_peaks(&mut self) {
+        if !self.rules.variant.builds_on_waste() {
+            return;
+        }
+        let faces: Vec<(u8, Face)> = self
+            .pyramid
+            .keys()
+            .map(|i| {
+                let face = if self.is_uncovered(*i) {
+                    Face::Up
+                } else {
+                    Face::Down
+                };
+                (*i, face)
+            })
+            .collect();
+        for (i, face) in faces {
+            if let Some(card) = self.pyramid.get_mut(&i).and_then(|s| s.peek_mut(Side::Top)) {
+                card.set_face(face);
+            }
+        }
+    }
+    /// Foundation the Pyramid pairs are discarded to
+    pub fn discard_mut(&mut self) -> Result<&mut dyn PileBehavior, GameError> {
+        self.pile_mut(PileId::Foundation(0))
+    }
+
+    /// Spider: first column topped by a complete King to Ace run, with a foundation to take it
+    pub fn complete_run_move(&self) -> Option<Action> {
+        let mut columns: Vec<&Column> = self.columns.values().collect();
+        columns.sort_by_key(|c| c.id());
+        let (from, ace) = columns.into_iter().find_map(|c| {
+            let ace = c.peek(Side::Top).filter(|_| c.has_complete_run())?;
+            Some((c.id(), ace))
+        })?;
+        let to = self
+            .foundation_ids()
+            .into_iter()
+            .find(|id| self.accepts(*id, ace))?;
+        Some(Action::Move {
+            num_cards: 13,
+            from,
+            to,
+        })
+    }
+
     pub fn is_won(&self) -> bool {
+        // The stock and waste may be left over once the tableau is cleared
+        if self.rules.variant.removes_pairs() || self.rules.variant.builds_on_waste() {
+            return self.pyramid.values().all(|s| s.is_empty())
+                && self.columns.values().all(|c| c.is_empty());
+        }
         if !self.stock.is_empty() || !self.waste.is_empty() {
             return false;
         }
@@ -205,6 +580,6 @@ impl GameState {
                 return false;
             }
         }
-        true
+        self.cells.values().all(|c| c.is_empty())
     }
 }
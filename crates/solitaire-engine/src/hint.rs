@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use solitaire_core::prelude::*;
+
+use crate::{action::Action, game_state::GameState};
+
+/// Suggested action with the reason it was picked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hint {
+    pub action: Action,
+    pub reason: String,
+    /// Higher is better
+    pub score: i32,
+}
+
+impl Hint {
+    /// Scores `action` taking `before` to `after`, `None` if it is not worth suggesting
+    pub fn rate(before: &GameState, action: &Action, after: &GameState) -> Option<Self> {
+        let (score, reason) = match *action {
+            Action::Draw if Self::stock_helps(before) => (5, "Draw from the stock".to_string()),
+            Action::Recycle if Self::stock_helps(before) => {
+                (1, "Recycle the waste into the stock".to_string())
+            }
+            Action::Move {
+                num_cards,
+                from,
+                to,
+            } => Self::rate_move(before, num_cards, from, to, after)?,
+            Action::RemovePair { first, second } => {
+                let card = |id| before.available_card(id);
+                let cards = [Some(first), second].into_iter().flatten();
+                let from_pyramid = cards
+                    .clone()
+                    .filter(|id| matches!(id, PileId::Pyramid(_)))
+                    .count() as i32;
+                let names: Vec<String> = cards.filter_map(card).map(|c| describe(&c)).collect();
+                (
+                    30 + 10 * from_pyramid,
+                    format!("Remove {}", names.join(" and ")),
+                )
+            }
+            _ => return None,
+        };
+        Some(Self {
+            action: action.clone(),
+            reason,
+            score,
+        })
+    }
+
+    /// Whether going through the stock can turn up a card with somewhere to go. Spider,
+    /// Pyramid, Golf and TriPeaks play what they draw in ways this does not check
+    fn stock_helps(before: &GameState) -> bool {
+        let variant = before.rules.variant;
+        if !variant.has_waste() || variant.removes_pairs() || variant.builds_on_waste() {
+            return true;
+        }
+        let mut targets = before.foundation_ids();
+        targets.extend(before.column_ids());
+        before
+            .stock
+            .cards()
+            .iter()
+            .chain(before.waste.cards())
+            .any(|card| targets.iter().any(|&to| before.accepts(to, *card)))
+    }
+
+    fn rate_move(
+        before: &GameState,
+        num_cards: usize,
+        from: PileId,
+        to: PileId,
+        after: &GameState,
+    ) -> Option<(i32, String)> {
+        let from_pile = before.pile(from).ok()?;
+        let moved = from_pile.peek_index(from_pile.len().checked_sub(num_cards)?)?;
+        let below = from_pile
+            .len()
+            .checked_sub(num_cards + 1)
+            .and_then(|i| from_pile.peek_index(i));
+        let from_column = matches!(from, PileId::Column(_));
+        let reveals = from_column && below.is_some_and(|c| *c.face() == Face::Down);
+        let empties = from_column && below.is_none();
+
+        let mut score = 0;
+        let mut reasons = vec![];
+
+        match (from, to) {
+            // Taking cards back from a foundation only undoes progress
+            (PileId::Foundation(_), _) => return None,
+            (_, PileId::Foundation(_)) => {
+                score += 40;
+                if before.is_safe_to_foundation(&moved) {
+                    score += 10;
+                }
+                reasons.push(format!("moves {} to a foundation", describe(&moved)));
+            }
+            (PileId::Waste, PileId::Column(_)) => {
+                score += 20;
+                reasons.push(format!("plays {} from the waste", describe(&moved)));
+            }
+            // Golf and TriPeaks, the only moves there are
+            (PileId::Column(_) | PileId::Pyramid(_), PileId::Waste) => {
+                score += 20;
+                reasons.push(format!("plays {} onto the waste", describe(&moved)));
+                let turned = before
+                    .pyramid
+                    .keys()
+                    .filter(|i| !before.is_uncovered(**i) && after.is_uncovered(**i))
+                    .count();
+                if turned > 0 {
+                    score += 25 * turned as i32;
+                    reasons.push(format!("uncovers {turned} peak card(s)"));
+                }
+            }
+            (PileId::Cell(_), PileId::Column(_)) => {
+                score += 20;
+                reasons.push(format!("frees the cell holding {}", describe(&moved)));
+            }
+            // Parking a card is only worth it when what it uncovers can go home
+            (PileId::Column(_), PileId::Cell(_)) => {
+                let exposed = below?;
+                let playable = after
+                    .foundation_ids()
+                    .into_iter()
+                    .any(|id| after.accepts(id, exposed));
+                if !playable {
+                    return None;
+                }
+                score += 5;
+                reasons.push(format!("frees {} for a foundation", describe(&exposed)));
+            }
+            (PileId::Column(_), PileId::Column(_)) => {
+                let to_empty = before.pile(to).ok()?.is_empty();
+                // Moving a whole column into an empty one changes nothing
+                if empties && to_empty {
+                    return None;
+                }
+                // Shifting a run between columns without uncovering anything is pointless,
+                // unless the card left on top can then go to a foundation
+                if !reveals && !empties {
+                    let exposed = below?;
+                    let playable = after
+                        .foundation_ids()
+                        .into_iter()
+                        .any(|id| after.accepts(id, exposed));
+                    if !playable {
+                        return None;
+                    }
+                    score += 15;
+                    reasons.push(format!("frees {} for a foundation", describe(&exposed)));
+                }
+            }
+            _ => return None,
+        }
+
+        if reveals {
+            score += 50;
+            reasons.push("reveals a face-down card".to_string());
+        }
+        if empties {
+            score += 30;
+            reasons.push("empties a column".to_string());
+        }
+
+        let mut reason = reasons.join(" and ");
+        if let Some(first) = reason.get_mut(0..1) {
+            first.make_ascii_uppercase();
+        }
+        Some((score, reason))
+    }
+}
+
+/// e.g. "Queen of hearts"
+fn describe(card: &Card) -> String {
+    format!("{:?} of {}s", card.rank(), card.suit())
+}
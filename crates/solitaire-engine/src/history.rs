@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+use solitaire_core::prelude::*;
+
+use crate::{action::Action, error::GameError, game_state::GameState};
+
+/// One change made to a `GameState`, with what is needed to revert it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MoveRecord {
+    Draw {
+        count: usize,
+        prev_fanned: usize,
+    },
+    Recycle {
+        count: usize,
+        prev_fanned: usize,
+    },
+    /// Spider deal, one card onto each of the first `count` columns
+    DealRow {
+        count: usize,
+    },
+    Move {
+        num_cards: usize,
+        from: PileId,
+        to: PileId,
+        /// Whether the card left on top of `from` was turned face up
+        flipped: bool,
+        prev_fanned: usize,
+    },
+    /// Pyramid pair (or King) discarded to the foundation
+    RemovePair {
+        first: PileId,
+        second: Option<PileId>,
+        prev_fanned: usize,
+    },
+}
+impl MoveRecord {
+    /// Action that makes this change again
+    pub fn action(&self) -> Action {
+        match *self {
+            MoveRecord::Draw { .. } | MoveRecord::DealRow { .. } => Action::Draw,
+            MoveRecord::Recycle { .. } => Action::Recycle,
+            MoveRecord::Move {
+                num_cards,
+                from,
+                to,
+                ..
+            } => Action::Move {
+                num_cards,
+                from,
+                to,
+            },
+            MoveRecord::RemovePair { first, second, .. } => Action::RemovePair { first, second },
+        }
+    }
+}
+
+/// Everything one player action changed: the action itself plus automatic follow-up moves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub records: Vec<MoveRecord>,
+    /// Score before the entry was played, restored on undo
+    #[serde(default)]
+    pub score_before: i32,
+    /// TriPeaks streak before the entry, restored on undo
+    #[serde(default)]
+    pub streak_before: u32,
+}
+
+impl GameState {
+    /// Result of `action` on a copy of this state
+    pub fn test_action(&self, action: &Action) -> Result<GameState, GameError> {
+        let mut state = self.clone();
+        state.apply(action)?;
+        Ok(state)
+    }
+
+    /// Applies a non-meta action and records how to revert it
+    pub fn apply(&mut self, action: &Action) -> Result<MoveRecord, GameError> {
+        let prev_fanned = self.waste.fanned();
+        match *action {
+            Action::Draw => {
+                let before = self.stock.len();
+                self.draw()?;
+                let count = before - self.stock.len();
+                if self.rules.variant.has_waste() {
+                    Ok(MoveRecord::Draw { count, prev_fanned })
+                } else {
+                    Ok(MoveRecord::DealRow { count })
+                }
+            }
+            Action::Recycle => {
+                let count = self.waste.len();
+                self.recycle()?;
+                Ok(MoveRecord::Recycle { count, prev_fanned })
+            }
+            Action::Move {
+                num_cards,
+                from,
+                to,
+            } => {
+                // Card that ends up on top of `from`, flipped by the move if face down
+                let below_face_down = {
+                    let pile = self.pile(from)?;
+                    pile.len()
+                        .checked_sub(num_cards + 1)
+                        .and_then(|i| pile.peek_index(i))
+                        .is_some_and(|c| *c.face() == Face::Down)
+                };
+                self.move_cards(num_cards, from, to)?;
+                Ok(MoveRecord::Move {
+                    num_cards,
+                    from,
+                    to,
+                    flipped: below_face_down && matches!(from, PileId::Column(_)),
+                    prev_fanned,
+                })
+            }
+            Action::RemovePair { first, second } => {
+                self.remove_pair(first, second)?;
+                Ok(MoveRecord::RemovePair {
+                    first,
+                    second,
+                    prev_fanned,
+                })
+            }
+            _ => Err(GameError::InvalidMove),
+        }
+    }
+
+    /// Reverts a change previously made by `apply` (must be the latest one not yet reverted)
+    pub fn revert(&mut self, record: &MoveRecord) -> Result<(), GameError> {
+        match *record {
+            MoveRecord::Draw { count, prev_fanned } => {
+                for _ in 0..count {
+                    let card = self
+                        .waste
+                        .take_card(Side::Top)
+                        .map_err(GameError::PileError)?;
+                    self.stock.raw_insert_card(card, Side::Top, Face::Down);
+                }
+                self.waste.set_fanned(prev_fanned);
+            }
+            MoveRecord::DealRow { count } => {
+                for id in self.column_ids().into_iter().take(count).rev() {
+                    let card = self
+                        .pile_mut(id)?
+                        .take_card(Side::Top)
+                        .map_err(GameError::PileError)?;
+                    self.stock.raw_insert_card(card, Side::Top, Face::Down);
+                }
+            }
+            MoveRecord::Recycle { count, prev_fanned } => {
+                for _ in 0..count {
+                    let card = self
+                        .stock
+                        .take_card(Side::Top)
+                        .map_err(GameError::PileError)?;
+                    self.waste.raw_insert_card(card, Side::Top, Face::Up);
+                }
+                self.recycles = self.recycles.saturating_sub(1);
+                self.waste.set_fanned(prev_fanned);
+            }
+            MoveRecord::Move {
+                num_cards,
+                from,
+                to,
+                flipped,
+                prev_fanned,
+            } => {
+                if flipped {
+                    self.pile_mut(from)?.flip_card_at(0, Side::Top, Face::Down);
+                }
+                let mut cards = self.pile_mut(to)?.raw_take_cards(num_cards, Side::Top);
+                // A run on a foundation is upside down already
+                if !matches!(to, PileId::Foundation(_)) {
+                    cards.reverse();
+                }
+                let from_pile = self.pile_mut(from)?;
+                for card in cards {
+                    from_pile.raw_insert_card(card, Side::Top, Face::Up);
+                }
+                // A TriPeaks card put back covers the cards below it again
+                self.turn_peaks();
+                self.waste.set_fanned(prev_fanned);
+            }
+            MoveRecord::RemovePair {
+                first,
+                second,
+                prev_fanned,
+            } => {
+                // The second card was discarded on top of the first
+                for id in [second, Some(first)].into_iter().flatten() {
+                    let card = self
+                        .discard_mut()?
+                        .take_card(Side::Top)
+                        .map_err(GameError::PileError)?;
+                    self.pile_mut(id)?
+                        .raw_insert_card(card, Side::Top, Face::Up);
+                }
+                self.waste.set_fanned(prev_fanned);
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,13 +1,29 @@
 pub mod action;
 pub mod actions_results;
+pub mod auto_play;
+pub mod clock;
+pub mod dealing;
 pub mod error;
 pub mod game;
 pub mod game_state;
+pub mod hint;
+pub mod history;
+pub mod notation;
+pub mod replay;
+pub mod score;
+pub mod stats;
+pub mod validation;
 
 pub mod prelude {
     pub use crate::action::*;
     pub use crate::actions_results::*;
+    pub use crate::clock::*;
     pub use crate::error::*;
     pub use crate::game::*;
     pub use crate::game_state::*;
+    pub use crate::hint::*;
+    pub use crate::history::*;
+    pub use crate::replay::*;
+    pub use crate::score::*;
+    pub use crate::stats::*;
 }
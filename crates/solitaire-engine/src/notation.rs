@@ -0,0 +1,153 @@
+//! Text notation of whole positions, see `solitaire_core::notation` for cards and piles.
+//!
+//! ```text
+//! rules: draw=1 passes=unlimited build=alternate empty=king foundations=any to-tableau=yes
+//! stock: #Kd #5h
+//! waste: 3c
+//! f0: Ah 2h
+//! c0: #Kd Qs Jh
+//! ```
+//!
+//! A `recycles: n` line follows the rules once the stock has been recycled. Variants without
+//! a stock or waste leave their lines out, FreeCell adds `cell0: 7h`… lines and Pyramid
+//! `p0: Kd`… lines.
+
+use solitaire_core::prelude::*;
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use crate::game_state::GameState;
+
+impl fmt::Display for GameState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "rules: {}", self.rules.to_notation())?;
+        if self.recycles > 0 {
+            writeln!(f, "recycles: {}", self.recycles)?;
+        }
+        if self.rules.variant.has_stock() {
+            writeln!(f, "{}", self.stock)?;
+        }
+        if self.rules.variant.has_waste() {
+            writeln!(f, "{}", self.waste)?;
+        }
+        for id in self.cell_ids() {
+            if let PileId::Cell(i) = id {
+                writeln!(f, "{}", self.cells[&i])?;
+            }
+        }
+        for id in self.pyramid_ids() {
+            if let PileId::Pyramid(i) = id {
+                writeln!(f, "{}", self.pyramid[&i])?;
+            }
+        }
+        for id in self.foundation_ids() {
+            if let PileId::Foundation(i) = id {
+                writeln!(f, "{}", self.foundations[&i])?;
+            }
+        }
+        for id in self.column_ids() {
+            if let PileId::Column(i) = id {
+                writeln!(f, "{}", self.columns[&i])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GameState {
+    type Err = NotationError;
+    /// Lines may come in any order, a missing stock or waste is empty
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rules = RuleSet::default();
+        let mut recycles = 0;
+        let mut stock = None;
+        let mut waste = None;
+        let mut columns = HashMap::new();
+        let mut foundations = HashMap::new();
+        let mut cells = HashMap::new();
+        let mut pyramid = HashMap::new();
+
+        for (i, text) in s.lines().enumerate() {
+            let line = i + 1;
+            if text.trim().is_empty() {
+                continue;
+            }
+            let Some((label, rest)) = text.split_once(':') else {
+                return Err(NotationError::new(line, 1, "expected 'label: ...'"));
+            };
+            let first_column = label.chars().count() + 2;
+            match label.trim() {
+                "rules" => rules = RuleSet::from_notation(rest, line, first_column)?,
+                "recycles" => {
+                    recycles = rest.trim().parse().map_err(|_| {
+                        NotationError::new(line, first_column, "expected a number of recycles")
+                    })?
+                }
+                label => {
+                    let (_, cards) = parse_pile_line(text, line)?;
+                    let first = match PileId::from_label(label) {
+                        Some(PileId::Stock) => {
+                            let mut pile = Stock::new(vec![]);
+                            fill_pile(&mut pile, cards);
+                            stock.replace(pile).is_none()
+                        }
+                        Some(PileId::Waste) => {
+                            let mut pile = Waste::new(vec![]);
+                            fill_pile(&mut pile, cards);
+                            pile.reset_fan();
+                            waste.replace(pile).is_none()
+                        }
+                        Some(PileId::Column(id)) => {
+                            let mut pile = Column::new(id, vec![]);
+                            fill_pile(&mut pile, cards);
+                            columns.insert(id, pile).is_none()
+                        }
+                        Some(PileId::Cell(id)) => {
+                            let pile = free_cell_from_parts(label, cards)
+                                .map_err(|message| NotationError::new(line, 1, message))?;
+                            cells.insert(id, pile).is_none()
+                        }
+                        Some(PileId::Pyramid(id)) => {
+                            let pile = pyramid_slot_from_parts(label, cards)
+                                .map_err(|message| NotationError::new(line, 1, message))?;
+                            pyramid.insert(id, pile).is_none()
+                        }
+                        _ if label.starts_with('f') => {
+                            let pile = foundation_from_parts(label, cards)
+                                .map_err(|message| NotationError::new(line, 1, message))?;
+                            let PileId::Foundation(id) = pile.id() else {
+                                unreachable!("foundation_from_parts only builds foundations");
+                            };
+                            foundations.insert(id, pile).is_none()
+                        }
+                        _ => {
+                            return Err(NotationError::new(
+                                line,
+                                1,
+                                format!("unknown pile '{label}'"),
+                            ));
+                        }
+                    };
+                    if !first {
+                        return Err(NotationError::new(
+                            line,
+                            1,
+                            format!("'{label}' given twice"),
+                        ));
+                    }
+                }
+            }
+        }
+
+        let mut state = GameState::new(
+            columns,
+            stock.unwrap_or_else(|| Stock::new(vec![])),
+            waste.unwrap_or_else(|| Waste::new(vec![])),
+            foundations,
+            rules,
+        );
+        state.cells.extend(cells);
+        state.pyramid.extend(pyramid);
+        state.recycles = recycles;
+        Ok(state)
+    }
+}
@@ -0,0 +1,303 @@
+use serde::{Deserialize, Serialize};
+use solitaire_core::prelude::*;
+
+use crate::{action::Action, error::ReplayError, game::Game, game_state::GameState};
+
+/// Action played `at_ms` milliseconds of play time into the game.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimedAction {
+    pub at_ms: u64,
+    pub action: Action,
+}
+
+/// Everything needed to play a game again: the deal and the actions taken.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub rules: RuleSet,
+    pub seed: u64,
+    #[serde(default)]
+    pub deal: DealAlgorithm,
+    #[serde(default)]
+    pub auto_play: bool,
+    /// Starting position in text notation, for games not dealt from `seed`
+    #[serde(default)]
+    pub position: Option<String>,
+    pub actions: Vec<TimedAction>,
+}
+
+impl Replay {
+    pub const VERSION: u32 = 1;
+    const TEXT_HEADER: &str = "solitaire-replay";
+
+    pub fn from_game(game: &Game) -> Self {
+        Self {
+            version: Self::VERSION,
+            rules: game.state.rules,
+            seed: game.seed(),
+            deal: game.deal(),
+            auto_play: game.auto_play(),
+            position: game.start_position().as_ref().map(|s| s.to_string()),
+            actions: game.log().clone(),
+        }
+    }
+
+    /// Game as dealt (or set up), before any action
+    pub fn start(&self) -> Result<Game, ReplayError> {
+        let mut game = match &self.position {
+            Some(position) => {
+                let state: GameState =
+                    position
+                        .parse()
+                        .map_err(|e: NotationError| ReplayError::Parse {
+                            line: e.line,
+                            message: format!("starting position: {}", e.message),
+                        })?;
+                Game::from_state(state, self.rules).map_err(ReplayError::InvalidPosition)?
+            }
+            None => Game::with_deal(Some(self.seed), self.rules, self.deal)
+                .map_err(|_| ReplayError::InvalidSeed(self.seed))?,
+        };
+        game.set_auto_play(self.auto_play);
+        Ok(game)
+    }
+
+    /// Plays every action, returning the state before the first one and after each
+    pub fn apply(&self) -> Result<Vec<GameState>, ReplayError> {
+        let mut game = self.start()?;
+        let mut states = Vec::with_capacity(self.actions.len() + 1);
+        states.push(game.state.clone());
+        for (index, timed) in self.actions.iter().enumerate() {
+            game.handle_action(timed.action.clone()).map_err(|error| {
+                ReplayError::IllegalAction {
+                    index,
+                    action: timed.action.clone(),
+                    error,
+                }
+            })?;
+            states.push(game.state.clone());
+        }
+        Ok(states)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("replays always serialize")
+    }
+    pub fn from_json(json: &str) -> Result<Self, ReplayError> {
+        let replay: Self =
+            serde_json::from_str(json).map_err(|e| ReplayError::Json(e.to_string()))?;
+        if replay.version != Self::VERSION {
+            return Err(ReplayError::UnsupportedVersion(replay.version));
+        }
+        if !replay.deal.seeds().contains(&replay.seed) {
+            let message = format!("{} is not a {} deal number", replay.seed, replay.deal);
+            return Err(ReplayError::Json(message));
+        }
+        Ok(replay)
+    }
+
+    /// Compact form, one line per action (the seed line ends with `microsoft` for such deals,
+    /// a set-up starting position comes as `position` lines before the actions):
+    ///
+    /// ```text
+    /// solitaire-replay 1
+    /// seed 42
+    /// rules draw=1 passes=unlimited build=alternate empty=king foundations=any to-tableau=yes
+    /// auto-play no
+    /// 0 d
+    /// 1530 m 1 w c3
+    /// ```
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "{} {}\nseed {}{}\nrules {}\nauto-play {}\n",
+            Self::TEXT_HEADER,
+            self.version,
+            self.seed,
+            match self.deal {
+                DealAlgorithm::Portable => "",
+                DealAlgorithm::Microsoft => " microsoft",
+            },
+            self.rules.to_notation(),
+            yes_no(self.auto_play),
+        );
+        if let Some(position) = &self.position {
+            for line in position.lines() {
+                text.push_str(&format!("position {line}\n"));
+            }
+        }
+        for timed in &self.actions {
+            let code = match &timed.action {
+                Action::Draw => "d".to_string(),
+                Action::Recycle => "r".to_string(),
+                Action::Undo => "u".to_string(),
+                Action::Redo => "y".to_string(),
+                // A reset starts the log over, so it never shows up in one
+                Action::Reset => continue,
+                Action::Move {
+                    num_cards,
+                    from,
+                    to,
+                } => format!("m {num_cards} {} {}", pile_code(*from), pile_code(*to)),
+                Action::RemovePair { first, second } => match second {
+                    Some(second) => format!("p {} {}", pile_code(*first), pile_code(*second)),
+                    None => format!("p {}", pile_code(*first)),
+                },
+            };
+            text.push_str(&format!("{} {code}\n", timed.at_ms));
+        }
+        text
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, ReplayError> {
+        let last_line = text.lines().count();
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .peekable();
+
+        let mut next_line = |key: &str| {
+            let (n, line) = lines
+                .next()
+                .ok_or_else(|| parse_error(last_line, format!("missing '{key}' line")))?;
+            match line.split_once(' ') {
+                Some((k, rest)) if k == key => Ok((n, rest.trim())),
+                _ => Err(parse_error(n, format!("expected '{key}'"))),
+            }
+        };
+
+        let (n, version) = next_line(Self::TEXT_HEADER)?;
+        let version = parse_number(n, version)?;
+        if version != Self::VERSION {
+            return Err(ReplayError::UnsupportedVersion(version));
+        }
+        let (n, seed) = next_line("seed")?;
+        let (seed, deal) = match seed.split_once(' ') {
+            Some((seed, "microsoft")) => (seed, DealAlgorithm::Microsoft),
+            Some((_, other)) => return Err(parse_error(n, format!("unknown deal '{other}'"))),
+            None => (seed, DealAlgorithm::Portable),
+        };
+        let seed = parse_number(n, seed)?;
+        if !deal.seeds().contains(&seed) {
+            return Err(parse_error(
+                n,
+                format!("{seed} is not a {deal} deal number"),
+            ));
+        }
+        let (n, rules) = next_line("rules")?;
+        let rules = RuleSet::from_notation(rules, n, 1).map_err(|e| parse_error(n, e.message))?;
+        let (n, auto_play) = next_line("auto-play")?;
+        let auto_play = parse_yes_no(n, auto_play)?;
+
+        let mut position = None;
+        while let Some((_, line)) = lines.next_if(|(_, line)| line.starts_with("position ")) {
+            let pile = line.trim_start_matches("position ").trim();
+            position
+                .get_or_insert_with(String::new)
+                .push_str(&format!("{pile}\n"));
+        }
+
+        let actions = lines
+            .map(|(n, line)| parse_action(n, line))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            version,
+            rules,
+            seed,
+            deal,
+            auto_play,
+            position,
+            actions,
+        })
+    }
+}
+
+fn parse_error(line: usize, message: impl Into<String>) -> ReplayError {
+    ReplayError::Parse {
+        line,
+        message: message.into(),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(line: usize, s: &str) -> Result<T, ReplayError> {
+    s.parse()
+        .map_err(|_| parse_error(line, format!("'{s}' is not a valid number")))
+}
+
+fn yes_no(b: bool) -> &'static str {
+    if b { "yes" } else { "no" }
+}
+fn parse_yes_no(line: usize, s: &str) -> Result<bool, ReplayError> {
+    match s {
+        "yes" => Ok(true),
+        "no" => Ok(false),
+        _ => Err(parse_error(line, format!("expected yes or no, got '{s}'"))),
+    }
+}
+
+fn pile_code(pile: PileId) -> String {
+    match pile {
+        PileId::Stock => "s".to_string(),
+        PileId::Waste => "w".to_string(),
+        PileId::Column(i) => format!("c{i}"),
+        PileId::Foundation(i) => format!("f{i}"),
+        PileId::Cell(i) => format!("cell{i}"),
+        PileId::Pyramid(i) => format!("p{i}"),
+    }
+}
+fn parse_pile(line: usize, s: &str) -> Result<PileId, ReplayError> {
+    let pile = match s {
+        "s" => PileId::Stock,
+        "w" => PileId::Waste,
+        _ if s.starts_with("cell") => PileId::Cell(parse_number(line, &s[4..])?),
+        _ => match s.split_at_checked(1) {
+            Some(("c", i)) => PileId::Column(parse_number(line, i)?),
+            Some(("f", i)) => PileId::Foundation(parse_number(line, i)?),
+            Some(("p", i)) => PileId::Pyramid(parse_number(line, i)?),
+            _ => return Err(parse_error(line, format!("unknown pile '{s}'"))),
+        },
+    };
+    Ok(pile)
+}
+
+fn parse_action(line: usize, s: &str) -> Result<TimedAction, ReplayError> {
+    let mut tokens = s.split_whitespace();
+    let at_ms = parse_number(line, tokens.next().unwrap_or_default())?;
+    let action = match tokens.next() {
+        Some("d") => Action::Draw,
+        Some("r") => Action::Recycle,
+        Some("u") => Action::Undo,
+        Some("y") => Action::Redo,
+        Some("m") => {
+            let (Some(n), Some(from), Some(to)) = (tokens.next(), tokens.next(), tokens.next())
+            else {
+                return Err(parse_error(
+                    line,
+                    "a move needs a count, a source and a target",
+                ));
+            };
+            Action::Move {
+                num_cards: parse_number(line, n)?,
+                from: parse_pile(line, from)?,
+                to: parse_pile(line, to)?,
+            }
+        }
+        Some("p") => {
+            let Some(first) = tokens.next() else {
+                return Err(parse_error(line, "a pair needs at least one pile"));
+            };
+            Action::RemovePair {
+                first: parse_pile(line, first)?,
+                second: tokens.next().map(|s| parse_pile(line, s)).transpose()?,
+            }
+        }
+        Some(code) => return Err(parse_error(line, format!("unknown action '{code}'"))),
+        None => return Err(parse_error(line, "missing action")),
+    };
+    if let Some(extra) = tokens.next() {
+        return Err(parse_error(line, format!("unexpected '{extra}'")));
+    }
+    Ok(TimedAction { at_ms, action })
+}
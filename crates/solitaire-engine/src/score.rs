@@ -0,0 +1,179 @@
+use serde::{Deserialize, Serialize};
+use solitaire_core::prelude::*;
+
+use crate::history::MoveRecord;
+
+/// How points are awarded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum ScoringMode {
+    /// Windows-style points, never below zero
+    #[default]
+    Standard,
+    /// Dollars: $52 buy-in, $5 back per card on a foundation
+    Vegas {
+        /// Carry the bankroll over from one game to the next
+        cumulative: bool,
+    },
+}
+impl std::fmt::Display for ScoringMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let string = match self {
+            Self::Standard => "standard",
+            Self::Vegas { cumulative: false } => "vegas",
+            Self::Vegas { cumulative: true } => "vegas (cumulative)",
+        };
+        write!(f, "{string}")
+    }
+}
+
+/// Points (or dollars) earned in the current game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Score {
+    pub mode: ScoringMode,
+    pub points: i32,
+    /// Vegas money carried in from previous games
+    pub bankroll: i32,
+    /// TriPeaks cards played onto the waste since the last draw
+    #[serde(default)]
+    pub streak: u32,
+}
+
+impl Score {
+    pub const VEGAS_BUY_IN: i32 = 52;
+
+    /// Starting score, `bankroll` only counts for cumulative Vegas
+    pub fn new(mode: ScoringMode, bankroll: i32) -> Self {
+        let (points, bankroll) = match mode {
+            ScoringMode::Standard => (0, 0),
+            ScoringMode::Vegas { cumulative: false } => (-Self::VEGAS_BUY_IN, 0),
+            ScoringMode::Vegas { cumulative: true } => (bankroll - Self::VEGAS_BUY_IN, bankroll),
+        };
+        Self {
+            mode,
+            points,
+            bankroll,
+            streak: 0,
+        }
+    }
+
+    /// Score for dealing the same game again. Cumulative Vegas keeps what this deal won or
+    /// lost and pays the buy-in again, as a new game would
+    pub fn restarted(&self) -> Self {
+        match self.mode {
+            ScoringMode::Vegas { cumulative: true } => Self::new(self.mode, self.points),
+            _ => Self::new(self.mode, self.bankroll),
+        }
+    }
+
+    /// Adds the points earned by `record`
+    pub fn add(&mut self, record: &MoveRecord, rules: &RuleSet) {
+        self.points += self.mode.points_for(record, rules);
+        // TriPeaks streak: the nth card played onto the waste without a draw earns 5·n
+        if rules.variant == Variant::TriPeaks {
+            match record {
+                MoveRecord::Move {
+                    to: PileId::Waste, ..
+                } => {
+                    if self.mode == ScoringMode::Standard {
+                        self.points += 5 * self.streak as i32;
+                    }
+                    self.streak += 1;
+                }
+                MoveRecord::Draw { .. } => self.streak = 0,
+                _ => {}
+            }
+        }
+        if self.mode == ScoringMode::Standard {
+            self.points = self.points.max(0);
+        }
+    }
+
+    /// Standard bonus for winning after `secs` seconds, as in Windows Solitaire
+    pub fn time_bonus(&self, secs: u64) -> i32 {
+        match self.mode {
+            ScoringMode::Standard if secs >= 30 => (700_000 / secs).min(i32::MAX as u64) as i32,
+            _ => 0,
+        }
+    }
+
+    /// Standard penalty of 2 points per 10 seconds played
+    pub fn time_penalty(&self, secs: u64) -> i32 {
+        match self.mode {
+            ScoringMode::Standard => (secs / 10 * 2).min(i32::MAX as u64) as i32,
+            _ => 0,
+        }
+    }
+
+    /// This score after `secs` seconds of play
+    pub fn with_time(&self, secs: u64) -> Self {
+        let mut score = *self;
+        if score.mode == ScoringMode::Standard {
+            score.points = (score.points - self.time_penalty(secs)).max(0);
+        }
+        score
+    }
+}
+impl std::fmt::Display for Score {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.mode {
+            ScoringMode::Standard => write!(f, "{}", self.points),
+            ScoringMode::Vegas { .. } if self.points < 0 => write!(f, "-${}", -self.points),
+            ScoringMode::Vegas { .. } => write!(f, "${}", self.points),
+        }
+    }
+}
+
+impl ScoringMode {
+    /// Points earned (or lost) by a single move
+    pub fn points_for(&self, record: &MoveRecord, rules: &RuleSet) -> i32 {
+        match self {
+            ScoringMode::Standard => match *record {
+                MoveRecord::Draw { .. } | MoveRecord::DealRow { .. } => 0,
+                MoveRecord::Recycle { .. } => {
+                    if rules.draw_count >= 3 {
+                        -20
+                    } else {
+                        -100
+                    }
+                }
+                MoveRecord::Move {
+                    num_cards,
+                    from,
+                    to,
+                    flipped,
+                    ..
+                } => {
+                    let points = match (from, to) {
+                        (PileId::Waste, PileId::Column(_)) => 5,
+                        (
+                            PileId::Waste | PileId::Column(_) | PileId::Cell(_),
+                            PileId::Foundation(_),
+                        ) => 10 * num_cards as i32,
+                        (PileId::Foundation(_), PileId::Column(_)) => -15,
+                        // Golf and TriPeaks
+                        (PileId::Column(_) | PileId::Pyramid(_), PileId::Waste) => 5,
+                        _ => 0,
+                    };
+                    if flipped { points + 5 } else { points }
+                }
+                MoveRecord::RemovePair { second, .. } => 10 * (1 + second.is_some() as i32),
+            },
+            ScoringMode::Vegas { .. } => match *record {
+                MoveRecord::Move {
+                    num_cards,
+                    from,
+                    to,
+                    ..
+                } => match (from, to) {
+                    (PileId::Foundation(_), PileId::Foundation(_)) => 0,
+                    (_, PileId::Foundation(_)) => 5 * num_cards as i32,
+                    (PileId::Foundation(_), _) => -5,
+                    (PileId::Column(_) | PileId::Pyramid(_), PileId::Waste) => 5,
+                    _ => 0,
+                },
+                MoveRecord::RemovePair { second, .. } => 5 * (1 + second.is_some() as i32),
+                _ => 0,
+            },
+        }
+    }
+}
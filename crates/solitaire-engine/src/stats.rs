@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use solitaire_core::prelude::*;
+use std::time::Duration;
+
+/// Results of every game played with one set of rules.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VariantStats {
+    pub rules: RuleSet,
+    pub started: u32,
+    pub won: u32,
+    /// Games left unfinished for a new one
+    pub abandoned: u32,
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    pub fewest_moves: Option<usize>,
+    pub fastest: Option<Duration>,
+    pub best_score: Option<i32>,
+}
+
+impl VariantStats {
+    pub fn new(rules: RuleSet) -> Self {
+        Self {
+            rules,
+            started: 0,
+            won: 0,
+            abandoned: 0,
+            current_streak: 0,
+            longest_streak: 0,
+            fewest_moves: None,
+            fastest: None,
+            best_score: None,
+        }
+    }
+    /// Share of started games that were won, from 0 to 100
+    pub fn win_percentage(&self) -> f32 {
+        if self.started == 0 {
+            0.0
+        } else {
+            self.won as f32 * 100.0 / self.started as f32
+        }
+    }
+}
+
+/// Player statistics, kept separately for every rule variant played.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerStats {
+    variants: Vec<VariantStats>,
+}
+
+impl PlayerStats {
+    pub fn variants(&self) -> &[VariantStats] {
+        &self.variants
+    }
+    pub fn for_rules(&self, rules: &RuleSet) -> Option<&VariantStats> {
+        self.variants.iter().find(|v| v.rules == *rules)
+    }
+    fn entry(&mut self, rules: &RuleSet) -> &mut VariantStats {
+        let index = match self.variants.iter().position(|v| v.rules == *rules) {
+            Some(index) => index,
+            None => {
+                self.variants.push(VariantStats::new(*rules));
+                self.variants.len() - 1
+            }
+        };
+        &mut self.variants[index]
+    }
+
+    pub fn game_started(&mut self, rules: &RuleSet) {
+        self.entry(rules).started += 1;
+    }
+    /// Records a win, keeping the best of each record
+    pub fn game_won(&mut self, rules: &RuleSet, moves: usize, time: Duration, score: i32) {
+        let stats = self.entry(rules);
+        stats.won += 1;
+        stats.current_streak += 1;
+        stats.longest_streak = stats.longest_streak.max(stats.current_streak);
+        stats.fewest_moves = Some(stats.fewest_moves.map_or(moves, |m| m.min(moves)));
+        stats.fastest = Some(stats.fastest.map_or(time, |t| t.min(time)));
+        stats.best_score = Some(stats.best_score.map_or(score, |s| s.max(score)));
+    }
+    /// Records a game left unfinished, which ends the streak
+    pub fn game_abandoned(&mut self, rules: &RuleSet) {
+        let stats = self.entry(rules);
+        stats.abandoned += 1;
+        stats.current_streak = 0;
+    }
+
+    /// Games started and won over all variants
+    pub fn totals(&self) -> (u32, u32) {
+        self.variants.iter().fold((0, 0), |(started, won), v| {
+            (started + v.started, won + v.won)
+        })
+    }
+    pub fn reset(&mut self) {
+        self.variants.clear();
+    }
+}
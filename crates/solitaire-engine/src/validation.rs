@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use solitaire_core::prelude::*;
+
+use crate::{error::Violation, game_state::GameState};
+
+impl GameState {
+    /// Everything that keeps this position from being one a dealt game could reach
+    pub fn violations(&self) -> Vec<Violation> {
+        let mut violations = vec![];
+        self.check_pile_ids(&mut violations);
+        self.check_cards(&mut violations);
+        self.check_foundations(&mut violations);
+        self.check_columns(&mut violations);
+        self.check_pyramid(&mut violations);
+        violations
+    }
+
+    fn check_pile_ids(&self, violations: &mut Vec<Violation>) {
+        let columns = self
+            .columns
+            .iter()
+            .map(|(key, column)| (PileId::Column(*key), column.id()));
+        let foundations = self
+            .foundations
+            .iter()
+            .map(|(key, foundation)| (PileId::Foundation(*key), foundation.id()));
+        let cells = self
+            .cells
+            .iter()
+            .map(|(key, cell)| (PileId::Cell(*key), cell.id()));
+        let pyramid = self
+            .pyramid
+            .iter()
+            .map(|(key, slot)| (PileId::Pyramid(*key), slot.id()));
+        let mut piles: Vec<(PileId, PileId)> = columns
+            .chain(foundations)
+            .chain(cells)
+            .chain(pyramid)
+            .collect();
+        // The stock and waste always exist, they only count when holding cards
+        for id in [PileId::Stock, PileId::Waste] {
+            if self.pile(id).is_ok_and(|p| !p.is_empty()) {
+                piles.push((id, id));
+            }
+        }
+        piles.sort();
+        let variant = self.rules.variant;
+        let expected = (0..variant.n_columns())
+            .map(PileId::Column)
+            .chain((0..variant.n_foundations()).map(PileId::Foundation))
+            .chain((0..variant.n_cells()).map(PileId::Cell))
+            .chain((0..variant.n_pyramid_slots()).map(PileId::Pyramid));
+        for id in expected {
+            if !piles.iter().any(|(key, _)| *key == id) {
+                violations.push(Violation::MissingPile(id));
+            }
+        }
+        for (key, found) in piles {
+            if !key.is_valid_in(variant) {
+                violations.push(Violation::InvalidPile(key));
+            } else if key != found {
+                violations.push(Violation::MismatchedPile { key, found });
+            }
+        }
+    }
+
+    /// Each card of the deck as many times as the deck holds it
+    fn check_cards(&self, violations: &mut Vec<Violation>) {
+        let mut piles = vec![PileId::Stock, PileId::Waste];
+        piles.extend(self.cell_ids());
+        piles.extend(self.pyramid_ids());
+        piles.extend(self.foundation_ids());
+        piles.extend(self.column_ids());
+        let mut counts: HashMap<(Suit, Rank), usize> = HashMap::new();
+        for id in piles {
+            if let Ok(pile) = self.pile(id) {
+                for card in pile.cards() {
+                    *counts.entry((*card.suit(), *card.rank())).or_default() += 1;
+                }
+            }
+        }
+        let mut expected: Vec<(Card, usize)> = vec![];
+        for card in self.rules.deck() {
+            match expected.iter_mut().find(|(c, _)| *c == card) {
+                Some((_, copies)) => *copies += 1,
+                None => expected.push((card, 1)),
+            }
+        }
+        for (card, copies) in expected {
+            let count = counts.get(&(*card.suit(), *card.rank())).copied();
+            match count.unwrap_or(0) {
+                n if n < copies => violations.push(Violation::MissingCard(card)),
+                n if n == copies => {}
+                count => violations.push(Violation::DuplicateCard { card, count }),
+            }
+        }
+    }
+
+    /// Face up, from the Ace up in one suit (the foundation's own if fixed)
+    fn check_foundations(&self, violations: &mut Vec<Violation>) {
+        // Pyramid discards pairs in any order
+        if self.rules.variant.removes_pairs() {
+            return;
+        }
+        for id in self.foundation_ids() {
+            let PileId::Foundation(i) = id else { continue };
+            let foundation = &self.foundations[&i];
+            let mut expected = Some(Rank::Ace);
+            for (index, card) in foundation.cards().iter().enumerate() {
+                if *card.face() == Face::Down {
+                    violations.push(Violation::FaceDownOnFoundation {
+                        foundation: i,
+                        index,
+                    });
+                }
+                if Some(*card.rank()) != expected || foundation.suit() != Some(*card.suit()) {
+                    violations.push(Violation::FoundationOutOfOrder {
+                        foundation: i,
+                        index,
+                        card: *card,
+                    });
+                    break;
+                }
+                expected = card.rank().higher();
+            }
+        }
+    }
+
+    /// Face-down cards sit under the face-up run, which the top card is part of
+    fn check_columns(&self, violations: &mut Vec<Violation>) {
+        for id in self.column_ids() {
+            let PileId::Column(i) = id else { continue };
+            let cards = self.columns[&i].cards();
+            let first_up = cards
+                .iter()
+                .position(|c| *c.face() == Face::Up)
+                .unwrap_or(cards.len());
+            for (index, card) in cards.iter().enumerate() {
+                if *card.face() == Face::Down && (index > first_up || first_up == cards.len()) {
+                    violations.push(Violation::ExposedFaceDown { column: i, index });
+                }
+            }
+        }
+    }
+}
+
+impl GameState {
+    /// Slots emptied only once nothing covers them
+    fn check_pyramid(&self, violations: &mut Vec<Violation>) {
+        for id in self.pyramid_ids() {
+            let PileId::Pyramid(i) = id else { continue };
+            let covered = self
+                .rules
+                .variant
+                .covering_slots(i)
+                .iter()
+                .any(|j| self.pyramid.get(j).is_some_and(|s| !s.is_empty()));
+            if self.pyramid[&i].is_empty() && covered {
+                violations.push(Violation::CoveredGap(i));
+            }
+        }
+    }
+}
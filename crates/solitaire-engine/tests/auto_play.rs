@@ -0,0 +1,63 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+mod common;
+use common::position;
+
+/// Foundations holding hearts and clubs up to the Nine, diamonds and spades up to `top`
+fn foundations(top: usize) -> String {
+    let ranks = [
+        "A", "2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K",
+    ];
+    let mut piles = String::new();
+    for (i, suit) in ["h", "c", "d", "s"].into_iter().enumerate() {
+        let n = if i < 2 { 9 } else { top };
+        let cards: Vec<String> = ranks[..n].iter().map(|r| format!("{r}{suit}")).collect();
+        piles.push_str(&format!("f{i}: {}\n", cards.join(" ")));
+    }
+    piles
+}
+
+#[test]
+fn auto_complete_wins_face_up_positions() {
+    let piles = foundations(13) + "c0: Kc Qh Jc 10h\nc1: Kh Qc Jh 10c\n";
+    let state = position(RuleSet::default(), &piles);
+    assert!(state.can_auto_complete());
+    let mut game = Game::from_state(state, RuleSet::default()).unwrap();
+    let actions = game.state.auto_complete_actions().unwrap();
+    assert_eq!(actions.len(), 8);
+    for action in actions {
+        game.handle_action(action).unwrap();
+    }
+    assert!(game.state.is_won());
+
+    // A face-down card has to be turned by the player first
+    let piles = foundations(13) + "c0: #Kc Qh Jc 10h\nc1: Kh Qc Jh 10c\n";
+    let state = position(RuleSet::default(), &piles);
+    assert!(!state.can_auto_complete());
+    assert!(state.auto_complete_actions().is_none());
+}
+
+#[test]
+fn one_undo_takes_back_the_cascade() {
+    let piles = foundations(12) + "c0: Kc Kd\nc1: Ks Qh Jc 10h\nc2: Kh Qc Jh 10c\n";
+    let state = position(RuleSet::default(), &piles);
+    let mut game = Game::from_state(state, RuleSet::default()).unwrap();
+    game.set_auto_play(true);
+    let before = game.state.to_string();
+
+    let message = game
+        .handle_action(Action::Move {
+            num_cards: 1,
+            from: PileId::Column(0),
+            to: PileId::Foundation(2),
+        })
+        .unwrap();
+    assert!(message.ends_with("(+9 auto)"), "{message}");
+    assert!(game.state.is_won());
+    assert_eq!(game.n_moves(), 1);
+
+    game.handle_action(Action::Undo).unwrap();
+    assert_eq!(game.state.to_string(), before);
+    assert!(!game.can_undo());
+}
@@ -0,0 +1,51 @@
+use std::{sync::Arc, time::Duration};
+
+use solitaire_engine::prelude::*;
+
+fn game_with_clock() -> (Game, ManualClock) {
+    let clock = ManualClock::new();
+    let mut game = Game::new(Some(1));
+    game.set_clock_source(Arc::new(clock.clone()));
+    (game, clock)
+}
+
+#[test]
+fn elapsed_follows_the_clock() {
+    let (game, clock) = game_with_clock();
+    assert_eq!(game.elapsed(), Duration::ZERO);
+    clock.advance(Duration::from_secs(42));
+    assert_eq!(game.elapsed(), Duration::from_secs(42));
+}
+
+#[test]
+fn paused_time_does_not_count() {
+    let (mut game, clock) = game_with_clock();
+    clock.advance(Duration::from_secs(10));
+    game.pause();
+    clock.advance(Duration::from_secs(100));
+    assert_eq!(game.elapsed(), Duration::from_secs(10));
+    game.resume();
+    clock.advance(Duration::from_secs(5));
+    assert_eq!(game.elapsed(), Duration::from_secs(15));
+}
+
+#[test]
+fn reset_restarts_the_clock_with_the_same_source() {
+    let (mut game, clock) = game_with_clock();
+    clock.advance(Duration::from_secs(30));
+    game.reset().unwrap();
+    assert_eq!(game.elapsed(), Duration::ZERO);
+    clock.advance(Duration::from_secs(3));
+    assert_eq!(game.elapsed(), Duration::from_secs(3));
+}
+
+#[test]
+fn standard_score_loses_two_points_per_ten_seconds() {
+    let (mut game, clock) = game_with_clock();
+    game.set_scoring(ScoringMode::Standard, 0);
+    assert_eq!(game.timed_score().points, 0);
+    clock.advance(Duration::from_secs(25));
+    // Never below zero
+    assert_eq!(game.timed_score().points, 0);
+    assert_eq!(game.score().time_penalty(25), 4);
+}
@@ -0,0 +1,37 @@
+//! Fixtures shared by the integration tests, each test using the ones it needs
+#![allow(dead_code)]
+
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+/// Position under `rules`, `piles` being its text notation after the rules line
+pub fn position(rules: RuleSet, piles: &str) -> GameState {
+    format!("rules: {}\n{piles}", rules.to_notation())
+        .parse()
+        .unwrap()
+}
+
+/// Deal `seed` after `turns` turns, each playing the first valid action `wanted` picks, or
+/// else turning the stock over
+pub fn played_game(
+    seed: u64,
+    rules: RuleSet,
+    turns: usize,
+    wanted: impl Fn(&Action) -> bool,
+) -> Game {
+    let mut game = Game::with_rules(Some(seed), rules);
+    for _ in 0..turns {
+        let action = game
+            .actions_results()
+            .all_valid()
+            .into_iter()
+            .find(|a| wanted(a))
+            .unwrap_or(if game.state.stock.is_empty() {
+                Action::Recycle
+            } else {
+                Action::Draw
+            });
+        let _ = game.handle_action(action);
+    }
+    game
+}
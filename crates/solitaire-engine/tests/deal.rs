@@ -0,0 +1,62 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+fn top(game: &Game, column: u8) -> Card {
+    game.state.columns[&column].peek(Side::Top).unwrap()
+}
+
+#[test]
+fn seed_one_layout_is_pinned() {
+    let game = Game::new(Some(1));
+    let tops: Vec<_> = (0..7).map(|i| top(&game, i)).collect();
+    let expected = [
+        (Rank::Nine, Suit::Spade),
+        (Rank::Seven, Suit::Spade),
+        (Rank::Four, Suit::Spade),
+        (Rank::Queen, Suit::Spade),
+        (Rank::Seven, Suit::Club),
+        (Rank::Ace, Suit::Spade),
+        (Rank::King, Suit::Club),
+    ];
+    for (card, (rank, suit)) in tops.iter().zip(expected) {
+        assert_eq!((*card.rank(), *card.suit()), (rank, suit));
+        assert_eq!(*card.face(), Face::Up);
+    }
+    assert_eq!(game.state.stock.len(), 24);
+}
+
+#[test]
+fn microsoft_deal_goes_through_the_game() {
+    let game = Game::with_deal(Some(1), RuleSet::default(), DealAlgorithm::Microsoft).unwrap();
+    // Deal #1 starts JD 2D 9H, the first card of each column ends on top
+    let card = top(&game, 0);
+    assert_eq!((*card.rank(), *card.suit()), (Rank::Jack, Suit::Diamond));
+    let card = top(&game, 1);
+    assert_eq!((*card.rank(), *card.suit()), (Rank::Two, Suit::Diamond));
+    assert_eq!(game.deal(), DealAlgorithm::Microsoft);
+}
+
+#[test]
+fn random_microsoft_deals_have_real_deal_numbers() {
+    for _ in 0..20 {
+        let game = Game::with_deal(None, RuleSet::default(), DealAlgorithm::Microsoft).unwrap();
+        assert!(game.seed() <= u32::MAX as u64);
+    }
+    assert!(matches!(
+        Game::with_deal(
+            Some(u32::MAX as u64 + 1),
+            RuleSet::default(),
+            DealAlgorithm::Microsoft
+        ),
+        Err(GameError::InvalidSeed(_))
+    ));
+    let mut replay = Replay::from_game(&Game::new(Some(1)));
+    replay.deal = DealAlgorithm::Microsoft;
+    replay.seed = u32::MAX as u64 + 1;
+    assert!(matches!(replay.start(), Err(ReplayError::InvalidSeed(_))));
+    let text = "solitaire-replay 1\nseed 4294967296 microsoft\nrules draw=1\nauto-play no\n";
+    assert!(matches!(
+        Replay::from_text(text),
+        Err(ReplayError::Parse { line: 2, .. })
+    ));
+}
@@ -0,0 +1,101 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+fn draw_three(seed: u64) -> Game {
+    let rules = RuleSet {
+        draw_count: 3,
+        ..RuleSet::default()
+    };
+    Game::with_rules(Some(seed), rules)
+}
+
+fn from_waste(game: &Game) -> Option<Action> {
+    game.actions_results().all_valid().into_iter().find(|a| {
+        matches!(
+            a,
+            Action::Move {
+                from: PileId::Waste,
+                ..
+            }
+        )
+    })
+}
+
+#[test]
+fn drawing_three_fans_three() {
+    let mut game = draw_three(7);
+    game.handle_action(Action::Draw).unwrap();
+    assert_eq!(game.state.waste.len(), 3);
+    assert_eq!(game.state.waste.fanned(), 3);
+    assert_eq!(game.actions_results().fanned(), 3);
+    assert_eq!(ActionsResults::from_game(&game).fanned(), 3);
+}
+
+#[test]
+fn playing_from_the_fan_narrows_it() {
+    // Draw until the top of a fresh fan has somewhere to go
+    let mut game = draw_three(7);
+    let action = loop {
+        game.handle_action(if game.state.stock.is_empty() {
+            Action::Recycle
+        } else {
+            Action::Draw
+        })
+        .unwrap();
+        if game.state.waste.fanned() == 3
+            && let Some(action) = from_waste(&game)
+        {
+            break action;
+        }
+    };
+    let top = game.state.waste.peek(Side::Top).unwrap();
+    game.handle_action(action).unwrap();
+    assert_eq!(game.state.waste.fanned(), 2);
+    assert_eq!(game.actions_results().fanned(), 2);
+    assert_ne!(game.state.waste.peek(Side::Top), Some(top));
+}
+
+/// Draws the whole stock onto the waste
+fn draw_all(game: &mut Game) {
+    while !game.state.stock.is_empty() {
+        game.handle_action(Action::Draw).unwrap();
+    }
+}
+
+#[test]
+fn recycling_stops_at_the_pass_limit() {
+    let rules = RuleSet {
+        pass_limit: PassLimit::Passes(2),
+        ..RuleSet::default()
+    };
+    let mut game = Game::with_rules(Some(7), rules);
+    draw_all(&mut game);
+    game.handle_action(Action::Recycle).unwrap();
+    assert_eq!(game.state.remaining_passes(), Some(0));
+    draw_all(&mut game);
+    assert!(matches!(
+        game.handle_action(Action::Recycle),
+        Err(GameError::PassLimitReached)
+    ));
+    let valid = game.actions_results().all_valid();
+    assert!(!valid.iter().any(|a| matches!(a, Action::Recycle)));
+}
+
+#[test]
+fn undoing_a_recycle_gives_the_pass_back() {
+    let rules = RuleSet {
+        pass_limit: PassLimit::Passes(3),
+        ..RuleSet::default()
+    };
+    let mut game = Game::with_rules(Some(7), rules);
+    draw_all(&mut game);
+    game.handle_action(Action::Recycle).unwrap();
+    assert_eq!(game.state.recycles, 1);
+    assert_eq!(game.state.remaining_passes(), Some(1));
+
+    game.handle_action(Action::Undo).unwrap();
+    assert_eq!(game.state.recycles, 0);
+    assert_eq!(game.state.remaining_passes(), Some(2));
+    game.handle_action(Action::Redo).unwrap();
+    assert_eq!(game.state.recycles, 1);
+}
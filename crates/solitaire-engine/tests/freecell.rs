@@ -0,0 +1,124 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+mod common;
+use common::position;
+
+fn freecell() -> RuleSet {
+    RuleSet::for_variant(Variant::FreeCell)
+}
+
+#[test]
+fn microsoft_deal_one_is_dealt_row_by_row() {
+    let game = Game::with_deal(Some(1), freecell(), DealAlgorithm::Microsoft).unwrap();
+    let state = &game.state;
+    assert_eq!(state.columns.len(), 8);
+    assert_eq!(state.cells.len(), 4);
+    assert!(state.stock.is_empty());
+    assert_eq!(state.columns[&0].to_string(), "c0: Jd Kd 2s 4c 3s 6d 6s");
+    assert_eq!(state.columns[&7].to_string(), "c7: 5h 3h 3c 7s 7d 10c");
+    assert!(state.violations().is_empty());
+}
+
+#[test]
+fn one_card_per_cell() {
+    let mut game = Game::with_rules(Some(3), freecell());
+    let to_cell = |to| Action::Move {
+        num_cards: 1,
+        from: PileId::Column(0),
+        to: PileId::Cell(to),
+    };
+    game.handle_action(to_cell(0)).unwrap();
+    assert!(game.handle_action(to_cell(0)).is_err());
+    game.handle_action(to_cell(1)).unwrap();
+    assert_eq!(game.state.columns[&0].len(), 5);
+
+    // Cells go back to the columns, and undo empties them again
+    let cards_in_cells = |game: &Game| game.state.cells.values().map(|c| c.len()).sum::<usize>();
+    assert_eq!(cards_in_cells(&game), 2);
+    game.handle_action(Action::Undo).unwrap();
+    game.handle_action(Action::Undo).unwrap();
+    assert_eq!(cards_in_cells(&game), 0);
+}
+
+#[test]
+fn supermoves_are_limited_by_free_cells_and_empty_columns() {
+    // A five-card run on c0, three cells taken, no empty column: (1 + 1) · 2^0 = 2
+    let columns =
+        "c0: 9s 8h 7s 6h 5s 4h\nc1: 10d\nc2: Kd\nc3: Kc\nc4: Kh\nc5: Ks\nc6: Qd\nc7: Qc\n";
+    let state = position(
+        freecell(),
+        &format!("cell0: 2c\ncell1: 2d\ncell2: 2s\n{columns}"),
+    );
+    assert_eq!(state.supermove_capacity(PileId::Column(1)), 2);
+    let run_onto_ten = |n| Action::Move {
+        num_cards: n,
+        from: PileId::Column(0),
+        to: PileId::Column(1),
+    };
+    assert!(matches!(
+        state.test_action(&run_onto_ten(6)),
+        Err(GameError::PileError(PileError::InvalidTakeTooMany {
+            max: 2,
+            ..
+        }))
+    ));
+
+    // Freeing the cells and a column: (4 + 1) · 2^1 = 10
+    let columns = "c0: 9s 8h 7s 6h 5s 4h\nc1: 10d\nc2: Kd\nc3: Kc\nc4: Kh\nc5: Ks\nc6: Qd\nc7:\n";
+    let state = position(freecell(), columns);
+    assert_eq!(state.supermove_capacity(PileId::Column(1)), 10);
+    // The empty target column does not count towards its own capacity
+    assert_eq!(state.supermove_capacity(PileId::Column(7)), 5);
+    let after = state.test_action(&run_onto_ten(6)).unwrap();
+    assert_eq!(after.columns[&1].len(), 7);
+}
+
+#[test]
+fn won_once_cells_are_empty_too() {
+    let mut state = position(freecell(), "cell0: Kd\nc0:\n");
+    assert!(!state.is_won());
+    state.cells.get_mut(&0).unwrap().cards_mut().clear();
+    assert!(state.is_won());
+}
+
+#[test]
+fn positions_and_replays_keep_the_cells() {
+    let mut game = Game::with_rules(Some(8), freecell());
+    game.handle_action(Action::Move {
+        num_cards: 1,
+        from: PileId::Column(2),
+        to: PileId::Cell(3),
+    })
+    .unwrap();
+    let text = game.state.to_string();
+    assert!(!text.contains("stock"), "{text}");
+    assert!(text.contains("cell3: "), "{text}");
+    let parsed: GameState = text.parse().unwrap();
+    assert_eq!(parsed.to_string(), text);
+
+    let replay = Replay::from_text(&Replay::from_game(&game).to_text()).unwrap();
+    assert_eq!(replay.rules.variant, Variant::FreeCell);
+    let states = replay.apply().unwrap();
+    assert_eq!(states.last().unwrap().to_string(), text);
+}
+
+#[test]
+fn failed_group_moves_leave_the_deal_alone() {
+    let group = |seed, to| {
+        let mut game = Game::with_rules(Some(seed), freecell());
+        let before = game.state.to_string();
+        let moved = game.handle_action(Action::Move {
+            num_cards: 2,
+            from: PileId::Column(0),
+            to,
+        });
+        assert!(moved.is_err(), "seed {seed}");
+        assert_eq!(game.state.to_string(), before, "seed {seed}");
+        assert!(game.state.violations().is_empty(), "seed {seed}");
+    };
+    // A cell holds a single card
+    group(3, PileId::Cell(0));
+    // The two cards are not in sequence, the first would fit but not the second
+    group(2, PileId::Column(4));
+}
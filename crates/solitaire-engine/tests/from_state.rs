@@ -0,0 +1,103 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+#[test]
+fn dealt_positions_are_accepted() {
+    let state = Game::new(Some(7)).state;
+    assert!(state.violations().is_empty());
+    let position = state.to_string();
+    let game = Game::from_state(state, RuleSet::default()).unwrap();
+    assert_eq!(game.state.to_string(), position);
+    assert!(game.start_position().is_some());
+}
+
+#[test]
+fn rules_are_applied_to_the_position() {
+    let rules = RuleSet {
+        draw_count: 3,
+        ..RuleSet::default()
+    };
+    let game = Game::from_state(Game::new(Some(7)).state, rules).unwrap();
+    assert_eq!(game.state.rules, rules);
+}
+
+#[test]
+fn every_violation_is_reported() {
+    let mut state = Game::new(Some(7)).state;
+    // Two cards from the stock: one lost, one put back on top of column 0
+    let lost = state.stock.cards_mut().pop_back().unwrap();
+    let mut copied = *state.stock.cards().back().unwrap();
+    copied.flip();
+    state
+        .columns
+        .get_mut(&0)
+        .unwrap()
+        .cards_mut()
+        .push_back(copied);
+    // Top of column 1 turned face down, leaving both its cards uncovered
+    state
+        .columns
+        .get_mut(&1)
+        .unwrap()
+        .cards_mut()
+        .back_mut()
+        .unwrap()
+        .flip();
+    // A column that does not exist in Klondike
+    let mut extra = state.columns[&6].clone();
+    extra.cards_mut().clear();
+    state.columns.insert(9, extra);
+    // A pile Klondike needs, taken away while still empty
+    state.foundations.remove(&3);
+
+    let violations = Game::from_state(state, RuleSet::default())
+        .err()
+        .unwrap()
+        .violations;
+    let same_card = |a: &Card, b: &Card| a.rank() == b.rank() && a.suit() == b.suit();
+    assert!(
+        violations
+            .iter()
+            .any(|v| matches!(v, Violation::InvalidPile(PileId::Column(9))))
+    );
+    assert!(
+        violations
+            .iter()
+            .any(|v| matches!(v, Violation::MissingCard(c) if same_card(c, &lost)))
+    );
+    assert!(violations.iter().any(
+        |v| matches!(v, Violation::DuplicateCard { card, count: 2 } if same_card(card, &copied))
+    ));
+    assert!(
+        violations
+            .iter()
+            .any(|v| matches!(v, Violation::ExposedFaceDown { column: 1, .. }))
+    );
+    assert!(
+        violations
+            .iter()
+            .any(|v| matches!(v, Violation::MissingPile(PileId::Foundation(3))))
+    );
+    assert_eq!(violations.len(), 6, "{violations:?}");
+}
+
+#[test]
+fn foundations_must_climb_from_the_ace() {
+    let mut state = Game::new(Some(7)).state;
+    let two = Card::new(Rank::Two, Suit::Heart, Face::Up);
+    state
+        .foundations
+        .get_mut(&0)
+        .unwrap()
+        .cards_mut()
+        .push_back(two);
+    let violations = state.violations();
+    assert!(violations.iter().any(|v| matches!(
+        v,
+        Violation::FoundationOutOfOrder {
+            foundation: 0,
+            index: 0,
+            ..
+        }
+    )));
+}
@@ -0,0 +1,169 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+mod common;
+use common::position;
+
+fn to_waste(from: PileId) -> Action {
+    Action::Move {
+        num_cards: 1,
+        from,
+        to: PileId::Waste,
+    }
+}
+
+/// Plays the first card that fits onto the waste, drawing when none does
+fn played_game(variant: Variant) -> Game {
+    common::played_game(8, RuleSet::for_variant(variant), 30, |a| {
+        matches!(a, Action::Move { .. })
+    })
+}
+
+#[test]
+fn golf_deals_seven_columns_of_five() {
+    let rules = RuleSet::for_variant(Variant::Golf);
+    assert!(rules.to_notation().starts_with("variant=golf wrap=no "));
+    let game = Game::with_rules(Some(4), rules);
+    let state = &game.state;
+    for column in state.columns.values() {
+        assert_eq!(column.len(), 5);
+        assert!(column.cards().iter().all(|c| *c.face() == Face::Up));
+    }
+    assert_eq!(state.waste.len(), 1);
+    assert_eq!(state.stock.len(), 16);
+    assert!(state.foundations.is_empty());
+    assert!(state.violations().is_empty(), "{:?}", state.violations());
+    let text = state.to_string();
+    assert_eq!(text.parse::<GameState>().unwrap().to_string(), text);
+}
+
+#[test]
+fn golf_builds_up_or_down_on_the_waste() {
+    let rules = RuleSet::for_variant(Variant::Golf);
+    let mut state = position(rules, "c0: 5h 8s\nc1: 9d\nwaste: 7c\n");
+    state.apply(&to_waste(PileId::Column(0))).unwrap();
+    state.apply(&to_waste(PileId::Column(1))).unwrap();
+    assert_eq!(state.waste.to_string(), "waste: 7c 8s 9d");
+    assert!(matches!(
+        state.test_action(&to_waste(PileId::Column(0))),
+        Err(GameError::NotAdjacent)
+    ));
+    // Nothing else moves
+    let column_move = Action::Move {
+        num_cards: 1,
+        from: PileId::Column(0),
+        to: PileId::Column(1),
+    };
+    assert!(state.test_action(&column_move).is_err());
+
+    let mut state = position(rules, "c0: Ah\nwaste: Kc\n");
+    assert!(state.test_action(&to_waste(PileId::Column(0))).is_err());
+    state.rules.wrap = true;
+    state.apply(&to_waste(PileId::Column(0))).unwrap();
+    assert!(state.is_won());
+}
+
+#[test]
+fn ranks_wrap_only_when_asked() {
+    assert_eq!(Rank::King.higher_wrapping(), Rank::Ace);
+    assert_eq!(Rank::Ace.lower_wrapping(), Rank::King);
+    assert!(Rank::Ace.is_adjacent(&Rank::Two, false));
+    assert!(!Rank::Ace.is_adjacent(&Rank::King, false));
+    assert!(Rank::Ace.is_adjacent(&Rank::King, true));
+    assert!(!Rank::Ace.is_adjacent(&Rank::Ace, true));
+}
+
+#[test]
+fn tripeaks_deals_three_peaks() {
+    let game = Game::with_rules(Some(4), RuleSet::for_variant(Variant::TriPeaks));
+    let state = &game.state;
+    assert_eq!(state.pyramid.len(), PEAKS_SIZE as usize);
+    for (i, slot) in &state.pyramid {
+        let face = *slot.peek(Side::Top).unwrap().face();
+        assert_eq!(face == Face::Up, *i >= 18, "slot {i}");
+    }
+    assert_eq!(state.waste.len(), 1);
+    assert_eq!(state.stock.len(), 23);
+    assert!(state.violations().is_empty(), "{:?}", state.violations());
+
+    assert_eq!(peaks_covering_slots(0), [3, 4]);
+    assert_eq!(peaks_covering_slots(2), [7, 8]);
+    assert_eq!(peaks_covering_slots(5), [12, 13]);
+    assert_eq!(peaks_covering_slots(8), [16, 17]);
+    assert_eq!(peaks_covering_slots(17), [26, 27]);
+    assert!(peaks_covering_slots(18).is_empty());
+}
+
+#[test]
+fn uncovered_peak_cards_turn_face_up() {
+    let rules = RuleSet::for_variant(Variant::TriPeaks);
+    let mut state = position(rules, "p9: #8h\np18: 3s\np19: 4d\nwaste: 2c\n");
+    assert!(matches!(
+        state.test_action(&to_waste(PileId::Pyramid(9))),
+        Err(GameError::InvalidMove)
+    ));
+    state.apply(&to_waste(PileId::Pyramid(18))).unwrap();
+    let record = state.apply(&to_waste(PileId::Pyramid(19))).unwrap();
+    assert_eq!(state.pyramid[&9].to_string(), "p9: 8h");
+
+    state.revert(&record).unwrap();
+    assert_eq!(state.pyramid[&9].to_string(), "p9: #8h");
+    assert_eq!(state.pyramid[&19].to_string(), "p19: 4d");
+}
+
+#[test]
+fn tripeaks_streaks_score_more_per_card() {
+    let rules = RuleSet::for_variant(Variant::TriPeaks);
+    let play = MoveRecord::Move {
+        num_cards: 1,
+        from: PileId::Pyramid(20),
+        to: PileId::Waste,
+        flipped: false,
+        prev_fanned: 0,
+    };
+    let draw = MoveRecord::Draw {
+        count: 1,
+        prev_fanned: 0,
+    };
+    let mut score = Score::new(ScoringMode::Standard, 0);
+    for _ in 0..3 {
+        score.add(&play, &rules);
+    }
+    assert_eq!((score.points, score.streak), (5 + 10 + 15, 3));
+    score.add(&draw, &rules);
+    score.add(&play, &rules);
+    assert_eq!((score.points, score.streak), (35, 1));
+
+    // Golf pays the same for every card
+    let mut score = Score::new(ScoringMode::Standard, 0);
+    for _ in 0..3 {
+        score.add(&play, &RuleSet::for_variant(Variant::Golf));
+    }
+    assert_eq!(score.points, 15);
+
+    // Undo gives the streak back
+    let mut game = played_game(Variant::TriPeaks);
+    while game.timed_score().streak == 0 {
+        game.handle_action(Action::Undo).unwrap();
+    }
+    let score = game.timed_score();
+    game.handle_action(Action::Draw).unwrap();
+    assert_eq!(game.timed_score().streak, 0);
+    game.handle_action(Action::Undo).unwrap();
+    assert_eq!(game.timed_score(), score);
+}
+
+#[test]
+fn replays_keep_the_waste_moves() {
+    for variant in [Variant::Golf, Variant::TriPeaks] {
+        let game = played_game(variant);
+        let text = Replay::from_game(&game).to_text();
+        assert!(text.contains(" w\n"), "{text}");
+        let states = Replay::from_text(&text).unwrap().apply().unwrap();
+        assert_eq!(
+            states.last().unwrap().to_string(),
+            game.state.to_string(),
+            "{variant}"
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+mod common;
+use common::position;
+
+#[test]
+fn hints_come_best_first() {
+    let state = position(
+        RuleSet::default(),
+        "f0: Ah\nc0: #9c 2h\nc1: 8d\nc2: #Kc 4s\nwaste: 7s\nstock: #3h\n",
+    );
+    let hints = ActionsResults::from_state(&state).ranked(&state);
+    let actions: Vec<String> = hints.iter().map(|h| h.action.to_string()).collect();
+    let expected = [
+        Action::Move {
+            num_cards: 1,
+            from: PileId::Column(0),
+            to: PileId::Foundation(0),
+        },
+        Action::Move {
+            num_cards: 1,
+            from: PileId::Waste,
+            to: PileId::Column(1),
+        },
+        Action::Draw,
+    ]
+    .map(|a| a.to_string());
+    assert_eq!(actions, expected);
+    assert!(hints.windows(2).all(|w| w[0].score > w[1].score));
+    assert_eq!(
+        hints[0].reason,
+        "Moves Two of hearts to a foundation and reveals a face-down card"
+    );
+}
+
+#[test]
+fn no_hint_when_nothing_helps() {
+    // Hearts and clubs stop at the Eight with both Nines under Kings, diamonds and spades
+    // at the Ten with the Jacks buried, and no column is free for a King
+    let mut piles = String::new();
+    for (i, suit) in ["h", "c", "d", "s"].into_iter().enumerate() {
+        let top = if i < 2 { 8 } else { 10 };
+        let ranks = [
+            "A", "2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K",
+        ];
+        let cards: Vec<String> = ranks[..top].iter().map(|r| format!("{r}{suit}")).collect();
+        piles.push_str(&format!("f{i}: {}\n", cards.join(" ")));
+    }
+    piles.push_str(
+        "c0: 9h Kc\nc1: 9c Kh\nc2: Jh Qh\nc3: Jd Jc Qc\nc4: Js 10c\nc5: Kd Qs\nc6: Ks Qd\nstock: #10h\n",
+    );
+    let state = position(RuleSet::default(), &piles);
+    let mut game = Game::from_state(state, RuleSet::default()).unwrap();
+
+    // Moves are left (Qh onto Kc, drawing the Ten), none worth making
+    assert!(!game.actions_results().all_valid().is_empty());
+    assert!(game.hint().is_none());
+    game.handle_action(Action::Draw).unwrap();
+    assert!(game.hint().is_none());
+}
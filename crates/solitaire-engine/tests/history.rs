@@ -0,0 +1,162 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+mod common;
+use common::position;
+
+/// Everything undo has to give back: the position, the waste fan and the score
+fn snapshot(game: &Game) -> (String, usize, Score) {
+    (
+        game.state.to_string(),
+        game.state.waste.fanned(),
+        game.score(),
+    )
+}
+
+/// Plays `action`, checks undo gets back to where it started and redo to where it ended
+fn round_trip(game: &mut Game, action: Action) {
+    let before = snapshot(game);
+    game.handle_action(action.clone()).unwrap();
+    let after = snapshot(game);
+    assert_ne!(before, after, "{action}");
+
+    game.handle_action(Action::Undo).unwrap();
+    assert_eq!(snapshot(game), before, "{action} undone");
+    game.handle_action(Action::Redo).unwrap();
+    assert_eq!(snapshot(game), after, "{action} redone");
+}
+
+/// Foundation holding `suit` from the Ace up to `top`
+fn foundation(suit: char, top: &str) -> String {
+    let ranks = [
+        "A", "2", "3", "4", "5", "6", "7", "8", "9", "T", "J", "Q", "K",
+    ];
+    let n = ranks.iter().position(|r| *r == top).unwrap() + 1;
+    let cards: Vec<String> = ranks[..n].iter().map(|r| format!("{r}{suit}")).collect();
+    cards.join(" ")
+}
+
+/// Late Klondike game, only the hearts and clubs from the Ten up left to play
+fn endgame() -> Game {
+    let piles = format!(
+        "f0: {}\nf1: {}\nf2: {}\nf3: {}\nc0: #Qh Tc\nc1: Kc\nc2: Jh\nc3: Kh Qc\nc4: Jc\nc5: Th\n",
+        foundation('h', "9"),
+        foundation('c', "9"),
+        foundation('d', "K"),
+        foundation('s', "K"),
+    );
+    Game::from_state(position(RuleSet::default(), &piles), RuleSet::default()).unwrap()
+}
+
+fn draw_three() -> Game {
+    let rules = RuleSet {
+        draw_count: 3,
+        ..RuleSet::default()
+    };
+    Game::with_rules(Some(7), rules)
+}
+
+#[test]
+fn draws_fan_the_waste_and_undo_folds_it_back() {
+    let mut game = draw_three();
+    round_trip(&mut game, Action::Draw);
+    assert_eq!(game.state.waste.fanned(), 3);
+    // The second draw replaces the fan of the first
+    round_trip(&mut game, Action::Draw);
+    assert_eq!(game.state.waste.len(), 6);
+}
+
+#[test]
+fn recycles_undo() {
+    let mut game = draw_three();
+    while !game.state.stock.is_empty() {
+        game.handle_action(Action::Draw).unwrap();
+    }
+    round_trip(&mut game, Action::Recycle);
+    assert!(game.state.waste.is_empty());
+}
+
+#[test]
+fn flipped_cards_turn_back_down() {
+    let mut game = endgame();
+    let to_foundation = Action::Move {
+        num_cards: 1,
+        from: PileId::Column(0),
+        to: PileId::Foundation(1),
+    };
+    round_trip(&mut game, to_foundation);
+    assert_eq!(game.state.columns[&0].to_string(), "c0: Qh");
+    game.handle_action(Action::Undo).unwrap();
+    assert_eq!(game.state.columns[&0].to_string(), "c0: #Qh 10c");
+}
+
+#[test]
+fn foundation_cards_come_back_down() {
+    let mut game = endgame();
+    let points = game.score().points;
+    round_trip(
+        &mut game,
+        Action::Move {
+            num_cards: 1,
+            from: PileId::Foundation(0),
+            to: PileId::Column(0),
+        },
+    );
+    assert_eq!(game.state.columns[&0].to_string(), "c0: #Qh 10c 9h");
+    game.handle_action(Action::Undo).unwrap();
+    assert_eq!(game.score().points, points);
+}
+
+#[test]
+fn auto_play_cascade_is_one_entry() {
+    let mut game = endgame();
+    game.set_auto_play(true);
+    round_trip(
+        &mut game,
+        Action::Move {
+            num_cards: 1,
+            from: PileId::Column(0),
+            to: PileId::Foundation(1),
+        },
+    );
+    assert!(game.state.is_won());
+    assert_eq!(game.n_moves(), 1);
+}
+
+#[test]
+fn new_moves_clear_redo() {
+    let mut game = draw_three();
+    game.handle_action(Action::Draw).unwrap();
+    game.handle_action(Action::Undo).unwrap();
+    game.handle_action(Action::Draw).unwrap();
+    assert!(matches!(
+        game.handle_action(Action::Redo),
+        Err(GameError::RedoUnavailable)
+    ));
+}
+
+#[test]
+fn undo_and_redo_availability_follows_the_history() {
+    let mut game = draw_three();
+    assert!(!game.can_undo() && !game.can_redo());
+    assert!(matches!(
+        game.handle_action(Action::Undo),
+        Err(GameError::UndoUnavailable)
+    ));
+
+    game.handle_action(Action::Draw).unwrap();
+    game.handle_action(Action::Draw).unwrap();
+    assert!(game.can_undo() && !game.can_redo());
+
+    game.handle_action(Action::Undo).unwrap();
+    game.handle_action(Action::Undo).unwrap();
+    assert!(!game.can_undo() && game.can_redo());
+    game.handle_action(Action::Redo).unwrap();
+    assert!(game.can_undo() && game.can_redo());
+    assert_eq!(game.n_moves(), 1);
+
+    // A new move drops the draw that was still waiting to be redone
+    game.handle_action(Action::Draw).unwrap();
+    assert!(game.can_undo() && !game.can_redo());
+    assert_eq!(game.n_moves(), 2);
+}
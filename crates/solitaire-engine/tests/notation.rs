@@ -0,0 +1,53 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+/// A game some way in, with cards in every kind of pile
+fn played_state() -> GameState {
+    let mut game = Game::new(Some(11));
+    for _ in 0..60 {
+        let action = game.hint().map(|hint| hint.action).unwrap_or(Action::Draw);
+        if game.handle_action(action).is_err() {
+            let _ = game.handle_action(Action::Recycle);
+        }
+    }
+    game.state
+}
+
+#[test]
+fn positions_round_trip() {
+    for state in [Game::new(Some(3)).state, played_state()] {
+        let text = state.to_string();
+        let parsed: GameState = text.parse().unwrap();
+        assert_eq!(parsed.to_string(), text);
+        assert_eq!(parsed.rules, state.rules);
+    }
+}
+
+#[test]
+fn rules_and_recycles_are_kept() {
+    let rules = RuleSet {
+        draw_count: 3,
+        pass_limit: PassLimit::Passes(3),
+        foundation_suits: FoundationSuits::Fixed,
+        ..RuleSet::default()
+    };
+    let mut state = Game::with_rules(Some(5), rules).state;
+    state.recycles = 2;
+    let parsed: GameState = state.to_string().parse().unwrap();
+    assert_eq!(parsed.rules, rules);
+    assert_eq!(parsed.recycles, 2);
+    assert_eq!(parsed.to_string(), state.to_string());
+}
+
+#[test]
+fn errors_report_line_and_column() {
+    let text = "stock: #Kd\nwaste:\nc0: Qs Jz\n";
+    let error = text.parse::<GameState>().err().unwrap();
+    assert_eq!((error.line, error.column), (3, 8));
+
+    let error = "c0: Ks\nc0: Qs\n".parse::<GameState>().err().unwrap();
+    assert_eq!(error.line, 2);
+
+    let error = "x9: Ks\n".parse::<GameState>().err().unwrap();
+    assert_eq!((error.line, error.column), (1, 1));
+}
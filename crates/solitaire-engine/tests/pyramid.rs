@@ -0,0 +1,131 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+mod common;
+
+fn position(piles: &str) -> GameState {
+    common::position(RuleSet::for_variant(Variant::Pyramid), piles)
+}
+
+fn pair(first: PileId, second: Option<PileId>) -> Action {
+    Action::RemovePair { first, second }
+}
+
+/// Removes whatever pair (or King) is legal first, drawing when there is none
+fn played_game() -> Game {
+    common::played_game(11, RuleSet::for_variant(Variant::Pyramid), 30, |a| {
+        matches!(a, Action::RemovePair { .. })
+    })
+}
+
+#[test]
+fn deal_fills_the_pyramid_and_the_stock() {
+    let game = Game::with_rules(Some(5), RuleSet::for_variant(Variant::Pyramid));
+    let state = &game.state;
+    assert_eq!(state.pyramid.len(), PYRAMID_SIZE as usize);
+    assert!(state.pyramid.values().all(|slot| slot.len() == 1));
+    assert!(state.columns.is_empty());
+    assert_eq!(state.stock.len(), 24);
+    assert!(state.violations().is_empty(), "{:?}", state.violations());
+
+    // Only the bottom row is free at the start
+    let free: Vec<PileId> = (21..28).map(PileId::Pyramid).collect();
+    assert_eq!(state.available_piles(), free);
+    assert_eq!(covering_slots(0), [1, 2]);
+    assert!(covering_slots(21).is_empty());
+
+    let text = state.to_string();
+    assert!(text.contains("\np27: "), "{text}");
+    assert_eq!(text.parse::<GameState>().unwrap().to_string(), text);
+}
+
+#[test]
+fn pairs_make_thirteen_and_uncover_the_card_above() {
+    let mut state = position("p15: Qc\np21: 6h\np22: 7s\np27: Kd\nwaste: Ah\nf0:\n");
+    assert!(matches!(
+        state.test_action(&pair(PileId::Pyramid(15), Some(PileId::Waste))),
+        Err(GameError::CardUnavailable(PileId::Pyramid(15)))
+    ));
+    assert!(matches!(
+        state.test_action(&pair(PileId::Pyramid(21), Some(PileId::Waste))),
+        Err(GameError::NotAPair)
+    ));
+    assert!(matches!(
+        state.test_action(&pair(PileId::Pyramid(21), None)),
+        Err(GameError::NotAPair)
+    ));
+
+    state
+        .apply(&pair(PileId::Pyramid(21), Some(PileId::Pyramid(22))))
+        .unwrap();
+    assert!(state.is_uncovered(15));
+    state
+        .apply(&pair(PileId::Pyramid(15), Some(PileId::Waste)))
+        .unwrap();
+    state.apply(&pair(PileId::Pyramid(27), None)).unwrap();
+
+    assert_eq!(state.foundations[&0].len(), 5);
+    assert!(state.waste.is_empty());
+    assert!(state.is_won());
+    // Nothing is ever moved between piles
+    let mut state = position("p21: 6h\np22: 7s\nf0:\n");
+    assert!(
+        state
+            .apply(&Action::Move {
+                num_cards: 1,
+                from: PileId::Pyramid(21),
+                to: PileId::Foundation(0),
+            })
+            .is_err()
+    );
+}
+
+#[test]
+fn covered_gaps_are_reported() {
+    // Slot 1 was cleared while slot 3 still rests on it
+    let state = position("p0: Qc\np3: 6h\nf0:\n");
+    assert!(state.violations().contains(&Violation::CoveredGap(1)));
+    assert!(!state.violations().contains(&Violation::CoveredGap(2)));
+}
+
+#[test]
+fn removals_score_and_undo() {
+    let mut game = Game::with_rules(Some(11), RuleSet::for_variant(Variant::Pyramid));
+    let dealt = game.state.to_string();
+    while !game
+        .actions_results()
+        .all_valid()
+        .iter()
+        .any(|a| matches!(a, Action::RemovePair { .. }))
+    {
+        game.handle_action(Action::Draw).unwrap();
+    }
+    let before = game.state.to_string();
+    let points = game.timed_score().points;
+    let removal = game
+        .actions_results()
+        .all_valid()
+        .into_iter()
+        .find(|a| matches!(a, Action::RemovePair { .. }))
+        .unwrap();
+    game.handle_action(removal).unwrap();
+    assert!(game.timed_score().points > points);
+
+    game.handle_action(Action::Undo).unwrap();
+    assert_eq!(game.state.to_string(), before);
+    assert_eq!(game.timed_score().points, points);
+    game.reset().unwrap();
+    assert_eq!(game.state.to_string(), dealt);
+}
+
+#[test]
+fn replay_text_keeps_the_removals() {
+    let game = played_game();
+    let replay = Replay::from_game(&game);
+    let text = replay.to_text();
+    assert!(text.lines().any(|line| line.contains(" p ")), "{text}");
+    let read = Replay::from_text(&text).unwrap();
+    assert_eq!(read.to_text(), text);
+    let states = read.apply().unwrap();
+    assert_eq!(states.last().unwrap().to_string(), game.state.to_string());
+}
@@ -0,0 +1,109 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+/// States have no `PartialEq`, compare their serialized form
+fn same(a: &GameState, b: &GameState) -> bool {
+    serde_json::to_value(a).unwrap() == serde_json::to_value(b).unwrap()
+}
+
+/// Game with a few draws, a recycle and some moves, ending on an undo. Each turn plays the
+/// hint, which never moves a card back and forth, or turns the stock over when there is none
+fn played_game() -> Game {
+    let rules = RuleSet {
+        draw_count: 3,
+        pass_limit: PassLimit::Passes(3),
+        ..RuleSet::default()
+    };
+    let mut game = Game::with_rules(Some(7), rules);
+    for _ in 0..40 {
+        let action = match game.hint() {
+            Some(hint) => hint.action,
+            None if game.state.stock.is_empty() => Action::Recycle,
+            None => Action::Draw,
+        };
+        let _ = game.handle_action(action);
+    }
+    game.handle_action(Action::Undo).unwrap();
+    game
+}
+
+#[test]
+fn replay_reaches_the_same_state() {
+    let game = played_game();
+    let replay = Replay::from_game(&game);
+    let states = replay.apply().unwrap();
+    assert_eq!(states.len(), replay.actions.len() + 1);
+    assert!(same(states.last().unwrap(), &game.state));
+}
+
+#[test]
+fn text_and_json_round_trip() {
+    let replay = Replay::from_game(&played_game());
+    let final_state = replay.apply().unwrap().pop().unwrap();
+
+    let from_text = Replay::from_text(&replay.to_text()).unwrap();
+    assert_eq!(from_text.to_text(), replay.to_text());
+    assert!(same(
+        &from_text.apply().unwrap().pop().unwrap(),
+        &final_state
+    ));
+
+    let from_json = Replay::from_json(&replay.to_json()).unwrap();
+    assert!(same(
+        &from_json.apply().unwrap().pop().unwrap(),
+        &final_state
+    ));
+}
+
+#[test]
+fn illegal_action_is_reported() {
+    let mut replay = Replay::from_game(&Game::new(Some(3)));
+    replay.actions.push(TimedAction {
+        at_ms: 0,
+        action: Action::Recycle,
+    });
+    assert!(matches!(
+        replay.apply(),
+        Err(ReplayError::IllegalAction { index: 0, .. })
+    ));
+}
+
+#[test]
+fn parse_errors_point_at_the_line() {
+    let text = "solitaire-replay 1\nseed 1\nrules draw=1\nauto-play no\n0 d\n5 m 1 q3 f0\n";
+    assert!(matches!(
+        Replay::from_text(text),
+        Err(ReplayError::Parse { line: 6, .. })
+    ));
+    assert!(matches!(
+        Replay::from_text("solitaire-replay 9\n"),
+        Err(ReplayError::UnsupportedVersion(9))
+    ));
+}
+
+#[test]
+fn set_up_positions_are_replayed() {
+    let position = Game::new(Some(21)).state.to_string();
+    let mut game = Game::from_state(position.parse().unwrap(), RuleSet::default()).unwrap();
+    game.handle_action(Action::Draw).unwrap();
+    game.handle_action(Action::Draw).unwrap();
+
+    let replay = Replay::from_text(&Replay::from_game(&game).to_text()).unwrap();
+    assert_eq!(replay.position.as_deref(), Some(position.as_str()));
+    let states = replay.apply().unwrap();
+    assert!(same(states.last().unwrap(), &game.state));
+}
+
+#[test]
+fn resets_start_the_replay_over() {
+    let mut game = played_game();
+    assert!(Replay::from_game(&game).to_text().contains(" r\n"));
+    game.reset().unwrap();
+    game.handle_action(Action::Draw).unwrap();
+    let text = Replay::from_game(&game).to_text();
+    assert_eq!(text.lines().filter(|l| l.ends_with(" d")).count(), 1);
+    assert!(!text.contains(" r\n"), "{text}");
+    assert!(
+        Replay::from_text("solitaire-replay 1\nseed 1\nrules draw=1\nauto-play no\n0 x\n").is_err()
+    );
+}
@@ -0,0 +1,49 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+mod common;
+use common::position;
+
+fn foundation_to_column() -> Action {
+    Action::Move {
+        num_cards: 1,
+        from: PileId::Foundation(0),
+        to: PileId::Column(0),
+    }
+}
+
+#[test]
+fn foundation_cards_come_back_only_when_allowed() {
+    let allowed = position(RuleSet::default(), "f0: Ah 2h\nc0: 3s\n");
+    assert!(allowed.test_action(&foundation_to_column()).is_ok());
+
+    let rules = RuleSet {
+        foundation_to_tableau: false,
+        ..RuleSet::default()
+    };
+    let refused = position(rules, "f0: Ah 2h\nc0: 3s\n");
+    assert!(matches!(
+        refused.test_action(&foundation_to_column()),
+        Err(GameError::InvalidMove)
+    ));
+}
+
+#[test]
+fn changed_rules_reach_the_piles() {
+    let mut state = position(RuleSet::default(), "f0:\nc0: 9s\nc1:\n");
+    let eight_of_clubs = "8c".parse().unwrap();
+    assert!(!state.accepts(PileId::Column(0), eight_of_clubs));
+    assert!(!state.accepts(PileId::Column(1), eight_of_clubs));
+
+    state.set_rules(RuleSet {
+        build_order: BuildOrder::AnySuit,
+        empty_column: EmptyColumnRule::Any,
+        foundation_suits: FoundationSuits::Fixed,
+        ..RuleSet::default()
+    });
+    assert!(state.accepts(PileId::Column(0), eight_of_clubs));
+    assert!(state.accepts(PileId::Column(1), eight_of_clubs));
+    // The empty foundation is pinned to the first fixed suit
+    assert_eq!(state.foundations[&0].fixed_suit(), Some(Suit::Heart));
+    assert!(!state.accepts(PileId::Foundation(0), "Ac".parse().unwrap()));
+}
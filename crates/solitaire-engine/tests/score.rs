@@ -0,0 +1,151 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+fn single(from: PileId, to: PileId, flipped: bool) -> MoveRecord {
+    MoveRecord::Move {
+        num_cards: 1,
+        from,
+        to,
+        flipped,
+        prev_fanned: 0,
+    }
+}
+
+fn recycle() -> MoveRecord {
+    MoveRecord::Recycle {
+        count: 24,
+        prev_fanned: 0,
+    }
+}
+
+#[test]
+fn each_move_kind_scores() {
+    let rules = RuleSet::default();
+    let standard = ScoringMode::Standard;
+    let vegas = ScoringMode::Vegas { cumulative: false };
+    let (waste, column, foundation) = (PileId::Waste, PileId::Column(0), PileId::Foundation(0));
+    let draw = MoveRecord::Draw {
+        count: 1,
+        prev_fanned: 0,
+    };
+    let cases = [
+        (draw, 0, 0),
+        (single(waste, column, false), 5, 0),
+        (single(waste, foundation, false), 10, 5),
+        (single(column, foundation, false), 10, 5),
+        (single(column, PileId::Column(1), false), 0, 0),
+        (single(column, PileId::Column(1), true), 5, 0),
+        (single(column, foundation, true), 15, 5),
+        (single(foundation, column, false), -15, -5),
+    ];
+    for (record, points, dollars) in cases {
+        assert_eq!(standard.points_for(&record, &rules), points, "{record:?}");
+        assert_eq!(vegas.points_for(&record, &rules), dollars, "{record:?}");
+    }
+
+    let rules = RuleSet::for_variant(Variant::Pyramid);
+    let pair = MoveRecord::RemovePair {
+        first: PileId::Pyramid(21),
+        second: Some(PileId::Waste),
+        prev_fanned: 0,
+    };
+    let king = MoveRecord::RemovePair {
+        first: PileId::Pyramid(21),
+        second: None,
+        prev_fanned: 0,
+    };
+    assert_eq!(standard.points_for(&pair, &rules), 20);
+    assert_eq!(standard.points_for(&king, &rules), 10);
+}
+
+#[test]
+fn recycling_costs_less_when_drawing_three() {
+    let draw_one = RuleSet::default();
+    let draw_three = RuleSet {
+        draw_count: 3,
+        ..RuleSet::default()
+    };
+    let standard = ScoringMode::Standard;
+    assert_eq!(standard.points_for(&recycle(), &draw_one), -100);
+    assert_eq!(standard.points_for(&recycle(), &draw_three), -20);
+    let vegas = ScoringMode::Vegas { cumulative: false };
+    assert_eq!(vegas.points_for(&recycle(), &draw_one), 0);
+}
+
+#[test]
+fn standard_score_stays_above_zero() {
+    let rules = RuleSet::default();
+    let mut score = Score::new(ScoringMode::Standard, 0);
+    score.add(&single(PileId::Waste, PileId::Foundation(0), false), &rules);
+    score.add(&recycle(), &rules);
+    assert_eq!(score.points, 0);
+
+    // Vegas debts are real
+    let mut score = Score::new(ScoringMode::Vegas { cumulative: false }, 0);
+    score.add(
+        &single(PileId::Foundation(0), PileId::Column(0), false),
+        &rules,
+    );
+    assert_eq!(score.points, -Score::VEGAS_BUY_IN - 5);
+}
+
+#[test]
+fn undo_gives_the_points_back() {
+    // Waste and foundation moves always score, draw until one comes up
+    let mut game = Game::new(Some(7));
+    while game.score().points == 0 {
+        let action = game
+            .actions_results()
+            .all_valid()
+            .into_iter()
+            .find(|a| {
+                matches!(
+                    a,
+                    Action::Move {
+                        from: PileId::Waste,
+                        ..
+                    }
+                ) || matches!(
+                    a,
+                    Action::Move {
+                        to: PileId::Foundation(_),
+                        ..
+                    }
+                )
+            })
+            .unwrap_or(Action::Draw);
+        game.handle_action(action).unwrap();
+    }
+    let points = game.score().points;
+    assert!(points > 0);
+
+    // The recycle penalty is cut short at zero, undo still restores the points before it
+    while !game.state.stock.is_empty() {
+        game.handle_action(Action::Draw).unwrap();
+    }
+    game.handle_action(Action::Recycle).unwrap();
+    assert_eq!(game.score().points, 0);
+    game.handle_action(Action::Undo).unwrap();
+    assert_eq!(game.score().points, points);
+}
+
+#[test]
+fn restarting_keeps_the_vegas_bankroll() {
+    let rules = RuleSet::default();
+    let cumulative = ScoringMode::Vegas { cumulative: true };
+    let mut score = Score::new(cumulative, 100);
+    assert_eq!(score.points, 48);
+    score.add(&single(PileId::Waste, PileId::Foundation(0), false), &rules);
+    let restarted = score.restarted();
+    assert_eq!(restarted.bankroll, 53);
+    assert_eq!(restarted.points, 1);
+
+    // A deal played again is bought in again, its losses stay lost
+    let restarted = Score::new(cumulative, 100).restarted().restarted();
+    assert_eq!(restarted.points, 100 - 3 * Score::VEGAS_BUY_IN);
+
+    let single_game = ScoringMode::Vegas { cumulative: false };
+    let restarted = Score::new(single_game, 0).restarted();
+    assert_eq!(restarted.points, -Score::VEGAS_BUY_IN);
+    assert_eq!(Score::new(ScoringMode::Standard, 0).restarted().points, 0);
+}
@@ -0,0 +1,131 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+mod common;
+use common::position;
+
+fn spider(suits: SuitCount) -> RuleSet {
+    RuleSet {
+        suits,
+        ..RuleSet::for_variant(Variant::Spider)
+    }
+}
+
+const RANKS: [&str; 13] = [
+    "A", "2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K",
+];
+
+/// Face-up spades of the given ranks, in order
+fn spades<'a>(ranks: impl Iterator<Item = &'a &'a str>) -> String {
+    ranks
+        .map(|rank| format!("{rank}s"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[test]
+fn two_decks_dealt_into_ten_columns() {
+    for suits in [SuitCount::One, SuitCount::Two, SuitCount::Four] {
+        let game = Game::with_rules(Some(5), spider(suits));
+        let state = &game.state;
+        let lens: Vec<usize> = (0..10).map(|i| state.columns[&i].len()).collect();
+        assert_eq!(lens, [6, 6, 6, 6, 5, 5, 5, 5, 5, 5]);
+        assert_eq!(state.stock.len(), 50);
+        assert_eq!(state.foundations.len(), 8);
+        assert!(state.columns.values().all(|c| c.max_take_count() == 1));
+        assert!(state.violations().is_empty(), "{:?}", state.violations());
+        let text = state.to_string();
+        assert!(!text.contains("waste"), "{text}");
+        assert_eq!(text.parse::<GameState>().unwrap().to_string(), text);
+    }
+    let one_suit = spider(SuitCount::One).deck();
+    assert_eq!(one_suit.len(), 104);
+    assert!(one_suit.iter().all(|c| *c.suit() == Suit::Spade));
+}
+
+#[test]
+fn only_same_suit_runs_move_together() {
+    let mut state = position(
+        spider(SuitCount::Four),
+        "c0: #Kc 9h 8s 7s\nc1: 9d\nc2: 10c\n",
+    );
+    assert_eq!(state.columns[&0].max_take_count(), 2);
+    let move_run = |num_cards, to| Action::Move {
+        num_cards,
+        from: PileId::Column(0),
+        to: PileId::Column(to),
+    };
+    assert!(state.test_action(&move_run(3, 2)).is_err());
+    // Any suit builds down, so the spade run goes onto the diamond
+    state.apply(&move_run(2, 1)).unwrap();
+    assert_eq!(state.columns[&1].to_string(), "c1: 9d 8s 7s");
+    state.apply(&move_run(1, 2)).unwrap();
+    assert_eq!(state.columns[&0].to_string(), "c0: Kc");
+}
+
+#[test]
+fn dealing_a_row_needs_every_column_filled() {
+    let mut game = Game::with_rules(Some(9), spider(SuitCount::Two));
+    let dealt = game.state.to_string();
+    game.handle_action(Action::Draw).unwrap();
+    assert_eq!(game.state.stock.len(), 40);
+    assert!(game.state.columns.values().all(|c| {
+        c.peek(Side::Top)
+            .is_some_and(|card| *card.face() == Face::Up)
+    }));
+    game.handle_action(Action::Undo).unwrap();
+    assert_eq!(game.state.to_string(), dealt);
+
+    let state = position(
+        spider(SuitCount::One),
+        "stock: #2s #3s #4s #5s #6s #7s #8s #9s #10s #Js\nc0: Ks\nc1:\n",
+    );
+    assert!(matches!(
+        state.test_action(&Action::Draw),
+        Err(GameError::EmptyColumnOnDeal)
+    ));
+}
+
+#[test]
+fn complete_runs_go_home_on_their_own() {
+    let mut piles = String::from("stock:\n");
+    for i in 0..8 {
+        let cards = if i < 6 {
+            spades(RANKS.iter())
+        } else {
+            String::new()
+        };
+        piles.push_str(&format!("f{i}: {cards}\n"));
+    }
+    // King to Two on c0, the Ace to finish it on c1, and another run over a face-down Ace
+    let king_to_two = spades(RANKS[1..].iter().rev());
+    piles.push_str(&format!(
+        "c0: {king_to_two}\nc1: As\nc2: #As {king_to_two}\n"
+    ));
+    let state = position(spider(SuitCount::One), &piles);
+    let mut game = Game::from_state(state, spider(SuitCount::One)).unwrap();
+    let start = game.state.to_string();
+
+    let play = |game: &mut Game, num_cards, from, to| {
+        game.handle_action(Action::Move {
+            num_cards,
+            from: PileId::Column(from),
+            to: PileId::Column(to),
+        })
+        .unwrap()
+    };
+    play(&mut game, 1, 1, 0);
+    assert!(game.state.columns[&0].is_empty());
+    assert_eq!(game.state.foundations[&6].len(), 13);
+    assert_eq!(game.score().points, 130);
+
+    // One undo takes back the move and the run it completed
+    game.handle_action(Action::Undo).unwrap();
+    assert_eq!(game.state.to_string(), start);
+    game.handle_action(Action::Redo).unwrap();
+
+    // Into the empty column, then the Ace it uncovered finishes the last run
+    play(&mut game, 12, 2, 0);
+    play(&mut game, 1, 2, 0);
+    assert!(game.state.is_won());
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+fn draw_three() -> RuleSet {
+    RuleSet {
+        draw_count: 3,
+        ..RuleSet::default()
+    }
+}
+
+#[test]
+fn wins_keep_the_best_records() {
+    let rules = RuleSet::default();
+    let mut stats = PlayerStats::default();
+    stats.game_started(&rules);
+    stats.game_won(&rules, 120, Duration::from_secs(300), 2500);
+    stats.game_started(&rules);
+    stats.game_won(&rules, 150, Duration::from_secs(200), 2000);
+
+    let record = stats.for_rules(&rules).unwrap();
+    assert_eq!((record.started, record.won), (2, 2));
+    assert_eq!((record.current_streak, record.longest_streak), (2, 2));
+    assert_eq!(record.fewest_moves, Some(120));
+    assert_eq!(record.fastest, Some(Duration::from_secs(200)));
+    assert_eq!(record.best_score, Some(2500));
+    assert_eq!(record.win_percentage(), 100.0);
+}
+
+#[test]
+fn losses_end_the_streak() {
+    let rules = RuleSet::default();
+    let mut stats = PlayerStats::default();
+    for _ in 0..2 {
+        stats.game_started(&rules);
+        stats.game_won(&rules, 100, Duration::from_secs(60), 1000);
+    }
+    stats.game_started(&rules);
+    stats.game_abandoned(&rules);
+    stats.game_started(&rules);
+
+    let record = stats.for_rules(&rules).unwrap();
+    assert_eq!(record.abandoned, 1);
+    assert_eq!((record.current_streak, record.longest_streak), (0, 2));
+    assert_eq!(record.win_percentage(), 50.0);
+    // Each set of rules keeps its own record
+    assert!(stats.for_rules(&draw_three()).is_none());
+}
+
+#[test]
+fn reset_forgets_every_variant() {
+    let mut stats = PlayerStats::default();
+    for rules in [RuleSet::default(), draw_three()] {
+        stats.game_started(&rules);
+        stats.game_won(&rules, 100, Duration::from_secs(60), 1000);
+    }
+    stats.game_started(&draw_three());
+    assert_eq!(stats.variants().len(), 2);
+    assert_eq!(stats.totals(), (3, 2));
+
+    stats.reset();
+    assert!(stats.variants().is_empty());
+    assert_eq!(stats.totals(), (0, 0));
+    assert_eq!(stats, PlayerStats::default());
+}
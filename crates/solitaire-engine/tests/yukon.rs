@@ -0,0 +1,75 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+mod common;
+use common::position;
+
+fn move_group(num_cards: usize) -> Action {
+    Action::Move {
+        num_cards,
+        from: PileId::Column(0),
+        to: PileId::Column(1),
+    }
+}
+
+#[test]
+fn every_card_is_dealt_to_the_columns() {
+    for variant in [Variant::Yukon, Variant::Russian] {
+        let game = Game::with_rules(Some(4), RuleSet::for_variant(variant));
+        let state = &game.state;
+        let lens: Vec<usize> = (0..7).map(|i| state.columns[&i].len()).collect();
+        assert_eq!(lens, [1, 6, 7, 8, 9, 10, 11]);
+        for (i, column) in (0..7).map(|i| (i, &state.columns[&i])) {
+            let face_down = column.cards().iter().filter(|c| *c.face() == Face::Down);
+            assert_eq!(face_down.count(), i as usize);
+        }
+        assert!(state.stock.is_empty());
+        assert!(state.violations().is_empty(), "{:?}", state.violations());
+        let text = state.to_string();
+        assert!(!text.contains("stock"), "{text}");
+        assert_eq!(text.parse::<GameState>().unwrap().to_string(), text);
+    }
+}
+
+#[test]
+fn any_face_up_group_moves_when_its_bottom_card_fits() {
+    let piles = "c0: #Kc 8h 3s Qd 7c\nc1: 9s\n";
+    let mut state = position(RuleSet::for_variant(Variant::Yukon), piles);
+    state.apply(&move_group(4)).unwrap();
+    assert_eq!(state.columns[&0].to_string(), "c0: Kc");
+    assert_eq!(state.columns[&1].to_string(), "c1: 9s 8h 3s Qd 7c");
+    // The bottom card still has to build on the destination
+    let state = position(RuleSet::for_variant(Variant::Yukon), piles);
+    assert!(state.test_action(&move_group(3)).is_err());
+
+    // Klondike rules check every card of the group
+    let klondike = position(RuleSet::default(), piles);
+    assert!(klondike.test_action(&move_group(4)).is_err());
+}
+
+#[test]
+fn russian_builds_in_suit() {
+    let rules = RuleSet::for_variant(Variant::Russian);
+    assert_eq!(rules.build_order, BuildOrder::SameSuit);
+    assert!(
+        position(rules, "c0: 8h 2c\nc1: 9s\n")
+            .test_action(&move_group(2))
+            .is_err()
+    );
+    let mut state = position(rules, "c0: 8s 2c\nc1: 9s\n");
+    state.apply(&move_group(2)).unwrap();
+    assert_eq!(state.columns[&1].to_string(), "c1: 9s 8s 2c");
+
+    // Undo puts a group back in its order
+    let mut game = Game::with_rules(Some(2), rules);
+    let dealt = game.state.to_string();
+    let group = game
+        .actions_results()
+        .all_valid()
+        .into_iter()
+        .find(|a| matches!(a, Action::Move { num_cards, .. } if *num_cards > 1))
+        .expect("a group move in this deal");
+    game.handle_action(group).unwrap();
+    game.handle_action(Action::Undo).unwrap();
+    assert_eq!(game.state.to_string(), dealt);
+}
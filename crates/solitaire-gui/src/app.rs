@@ -3,13 +3,15 @@ use serde::{Deserialize, Serialize};
 use crate::{
     app_stats::AppStats,
     card_textures::CardTextures,
-    modes::{mode::Mode, user_play::UserPlayMode},
+    modes::{editor::EditorMode, mode::Mode, replay::ReplayMode, user_play::UserPlayMode},
 };
 
 #[derive(Debug, Default, Deserialize, Serialize)]
 pub enum AppMode {
     #[default]
     UserPlay,
+    Replay,
+    Editor,
 }
 
 #[derive(Default, Deserialize, Serialize)]
@@ -17,6 +19,8 @@ pub enum AppMode {
 pub struct App {
     mode: AppMode,
     user_play_mode: UserPlayMode,
+    replay_mode: ReplayMode,
+    editor_mode: EditorMode,
     stats: AppStats,
 
     // Shared resources
@@ -53,6 +57,16 @@ impl eframe::App for App {
         {
             self.user_play_mode.card_textures = Some(app_textures.clone());
         }
+        if self.replay_mode.card_textures.is_none()
+            && let Some(app_textures) = &self.card_textures
+        {
+            self.replay_mode.card_textures = Some(app_textures.clone());
+        }
+        if self.editor_mode.card_textures.is_none()
+            && let Some(app_textures) = &self.card_textures
+        {
+            self.editor_mode.card_textures = Some(app_textures.clone());
+        }
 
         self.stats.update_frame();
 
@@ -65,6 +79,12 @@ impl eframe::App for App {
                 if ui.button("UserPlay").clicked() {
                     self.mode = AppMode::UserPlay;
                 }
+                if ui.button("Replay").clicked() {
+                    self.mode = AppMode::Replay;
+                }
+                if ui.button("Editor").clicked() {
+                    self.mode = AppMode::Editor;
+                }
                 ui.separator();
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     self.stats_ui(ui);
@@ -73,10 +93,18 @@ impl eframe::App for App {
         });
         match self.mode {
             AppMode::UserPlay => self.user_play_mode.update(),
+            AppMode::Replay => self.replay_mode.update(),
+            AppMode::Editor => self.editor_mode.update(),
         }
         egui::CentralPanel::default().show(ctx, |_ui| match self.mode {
             AppMode::UserPlay => self.user_play_mode.render(ctx),
+            AppMode::Replay => self.replay_mode.render(ctx),
+            AppMode::Editor => self.editor_mode.render(ctx),
         });
+        if let Some(game) = self.editor_mode.take_started_game() {
+            self.user_play_mode.load_game(game);
+            self.mode = AppMode::UserPlay;
+        }
 
         ctx.request_repaint();
     }
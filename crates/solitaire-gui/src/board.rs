@@ -0,0 +1,266 @@
+//! Drawing of a `GameState`, shared by every mode that shows a board.
+
+use egui::StrokeKind;
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+use crate::{card_textures::CardTextures, layout::Layout, ui_element::UiElement};
+
+/// Draws cards and empty piles, skipping the cards `hidden` returns true for
+pub fn render_ui_elements(
+    state: &GameState,
+    ui_elements: &[UiElement],
+    card_textures: Option<&CardTextures>,
+    ui: &mut egui::Ui,
+    hidden: impl Fn(PileId, &Card) -> bool,
+) {
+    for ui_element in ui_elements {
+        match ui_element {
+            UiElement::Card {
+                card, rect, pile, ..
+            } => {
+                if hidden(*pile, card) {
+                    continue;
+                }
+                render_card(card_textures, card, *rect, ui);
+            }
+            UiElement::EmptyPile { pile, rect, .. } => {
+                render_placeholder(*rect, ui);
+                if *pile == PileId::Stock
+                    && let Some(remaining) = state.remaining_passes()
+                {
+                    render_passes_left(remaining, *rect, ui);
+                }
+                if let PileId::Foundation(i) = pile
+                    && let Some(pile) = state.foundations.get(i)
+                {
+                    render_template(card_textures, pile.suit(), *rect, ui);
+                }
+            }
+        }
+    }
+}
+
+pub fn render_card(
+    card_textures: Option<&CardTextures>,
+    card: &Card,
+    rect: egui::Rect,
+    ui: &mut egui::Ui,
+) {
+    if let Some(card_textures) = card_textures {
+        let tex_opt = match card.face() {
+            Face::Down => Some(card_textures.get_back()),
+            Face::Up => card_textures.get(*card.suit(), *card.rank()),
+        };
+        if let Some(tex) = tex_opt {
+            ui.put(
+                rect,
+                egui::Image::new((tex.id(), rect.size())).fit_to_exact_size(rect.size()),
+            );
+        }
+    }
+}
+pub fn render_template(
+    card_textures: Option<&CardTextures>,
+    suit: Option<Suit>,
+    rect: egui::Rect,
+    ui: &mut egui::Ui,
+) {
+    let Some(suit) = suit else {
+        // Unclaimed foundation, any Ace may start it
+        ui.painter().text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            "A",
+            egui::FontId::proportional(32.0),
+            egui::Color32::LIGHT_GRAY,
+        );
+        return;
+    };
+    if let Some(card_textures) = card_textures
+        && let Some(tex) = card_textures.get_template(suit)
+    {
+        ui.put(
+            rect,
+            egui::Image::new((tex.id(), rect.size())).fit_to_exact_size(rect.size()),
+        );
+    }
+}
+pub fn render_passes_left(remaining: u32, rect: egui::Rect, ui: &mut egui::Ui) {
+    let text = match remaining {
+        0 => "No passes left".to_string(),
+        1 => "1 pass left".to_string(),
+        n => format!("{n} passes left"),
+    };
+    ui.painter().text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        text,
+        egui::FontId::proportional(14.0),
+        egui::Color32::LIGHT_GRAY,
+    );
+}
+pub fn render_placeholder(rect: egui::Rect, ui: &mut egui::Ui) {
+    ui.painter().rect_stroke(
+        rect,
+        5.0,
+        egui::Stroke::new(1.0, egui::Color32::LIGHT_GRAY),
+        StrokeKind::Inside,
+    );
+}
+
+enum PileLayout {
+    /// Cards stacked on top of each other, the top `fanned` ones spread to the right
+    Overlap {
+        fanned: usize,
+        spacing: f32,
+    },
+    Vertical {
+        spacing: f32,
+    },
+}
+
+/// Create UiElement entries for a pile
+fn push_pile_elements_generic(
+    layout: &Layout,
+    ui_elements: &mut Vec<UiElement>,
+    pile_id: PileId,
+    cards: &[Card], // ordered bottom → top
+    base_pos: (f32, f32),
+    pile_layout: PileLayout,
+) {
+    let base_rect = card_rect(layout, base_pos);
+
+    // Always push empty pile entry first
+    ui_elements.push(UiElement::empty_pile(pile_id, base_rect, cards.is_empty()));
+
+    match pile_layout {
+        PileLayout::Overlap { fanned, spacing } => {
+            let fan_start = cards.len().saturating_sub(fanned.max(1));
+            for (i, card) in cards.iter().enumerate() {
+                let is_top = i == cards.len().saturating_sub(1);
+                let interactible = is_top;
+                let offset = spacing * i.saturating_sub(fan_start) as f32;
+                let rect = card_rect(layout, (base_pos.0 + offset, base_pos.1));
+                ui_elements.push(UiElement::card(*card, pile_id, i, rect, interactible));
+            }
+        }
+
+        PileLayout::Vertical { spacing } => {
+            for (i, card) in cards.iter().enumerate() {
+                let pos = (base_pos.0, base_pos.1 + spacing * i as f32);
+                let rect = card_rect(layout, pos);
+                let interactible = card.face() == &Face::Up;
+                ui_elements.push(UiElement::card(*card, pile_id, i, rect, interactible));
+            }
+        }
+    }
+}
+
+/// Compute all ui elements (cards and empty piles)
+pub fn compute_ui_elements(state: &GameState, layout: &Layout) -> Vec<UiElement> {
+    let mut ui_elements: Vec<UiElement> = Vec::new();
+
+    // Stock, Waste
+    for pile_id in [PileId::Stock, PileId::Waste] {
+        if let Some(pos) = layout.get_position(&pile_id)
+            && let Ok(pile) = state.pile(pile_id)
+        {
+            let cards = pile.peek_cards(pile.len(), Side::Bottom);
+            let fanned = match pile_id {
+                PileId::Waste => state.waste.fanned(),
+                _ => 0,
+            };
+            push_pile_elements_generic(
+                layout,
+                &mut ui_elements,
+                pile_id,
+                &cards,
+                pos,
+                PileLayout::Overlap {
+                    fanned,
+                    spacing: layout.waste_fan_spacing,
+                },
+            );
+        }
+    }
+
+    // Cells and foundations
+    for pile_id in state.cell_ids().into_iter().chain(state.foundation_ids()) {
+        if let Some(pos) = layout.get_position(&pile_id)
+            && let Ok(pile_ref) = state.pile(pile_id)
+        {
+            let cards = pile_ref.peek_cards(pile_ref.len(), Side::Bottom);
+            push_pile_elements_generic(
+                layout,
+                &mut ui_elements,
+                pile_id,
+                &cards,
+                pos,
+                PileLayout::Overlap {
+                    fanned: 0,
+                    spacing: 0.0,
+                },
+            );
+        }
+    }
+
+    // Columns
+    for pile_id in state.column_ids() {
+        if let Some(pos) = layout.get_position(&pile_id)
+            && let Ok(pile_ref) = state.pile(pile_id)
+        {
+            let cards = pile_ref.peek_all(Side::Bottom);
+            push_pile_elements_generic(
+                layout,
+                &mut ui_elements,
+                pile_id,
+                &cards,
+                pos,
+                PileLayout::Vertical {
+                    spacing: layout.column_card_spacing,
+                },
+            );
+        }
+    }
+
+    // Pyramid, top row first so lower rows are drawn over it
+    for pile_id in state.pyramid_ids() {
+        if let Some(pos) = layout.get_position(&pile_id)
+            && let Ok(pile_ref) = state.pile(pile_id)
+        {
+            let first = ui_elements.len();
+            let cards = pile_ref.peek_all(Side::Bottom);
+            push_pile_elements_generic(
+                layout,
+                &mut ui_elements,
+                pile_id,
+                &cards,
+                pos,
+                PileLayout::Overlap {
+                    fanned: 0,
+                    spacing: 0.0,
+                },
+            );
+            // Covered cards cannot be picked up
+            if let PileId::Pyramid(i) = pile_id
+                && !state.is_uncovered(i)
+            {
+                for element in &mut ui_elements[first..] {
+                    if let UiElement::Card { interactible, .. } = element {
+                        *interactible = false;
+                    }
+                }
+            }
+        }
+    }
+
+    ui_elements
+}
+
+pub fn card_rect(layout: &Layout, position: (f32, f32)) -> egui::Rect {
+    egui::Rect::from_min_size(
+        egui::Pos2::from(position),
+        egui::vec2(layout.card_width, layout.card_height),
+    )
+}
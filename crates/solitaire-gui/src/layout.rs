@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
-use solitaire_core::pile::PileId;
-use solitaire_engine::game::Game;
+use solitaire_core::{pile::PileId, pyramid::PYRAMID_ROWS};
+use solitaire_engine::{game::Game, game_state::GameState};
 
 #[derive(Clone)]
 pub struct Layout {
@@ -9,10 +9,14 @@ pub struct Layout {
     pub card_width: f32,
     pub card_height: f32,
     pub column_card_spacing: f32,
+    pub waste_fan_spacing: f32,
 }
 
 impl Layout {
     pub fn new(game: &Game) -> Self {
+        Self::from_state(&game.state)
+    }
+    pub fn from_state(state: &GameState) -> Self {
         let card_width = 90.0;
         let card_height = 128.0;
         let spacing_x = 20.0;
@@ -20,23 +24,55 @@ impl Layout {
         let top_y = 60.0;
         let bot_y = 200.0;
         let column_card_spacing = 40.0;
+        let waste_fan_spacing = 20.0;
 
         let mut pile_positions = HashMap::new();
 
+        let variant = state.rules.variant;
+        let slot_x = |slot: u8| x_start + (card_width + spacing_x) * slot as f32;
+
         // Stock + Waste
-        pile_positions.insert(PileId::Stock, (x_start, top_y));
-        pile_positions.insert(PileId::Waste, (x_start + card_width + spacing_x, top_y));
+        if variant.has_stock() {
+            pile_positions.insert(PileId::Stock, (slot_x(0), top_y));
+        }
+        if variant.has_waste() {
+            pile_positions.insert(PileId::Waste, (slot_x(1), top_y));
+        }
 
-        // Foundations
-        for &id in game.state.foundations.keys() {
-            let x = x_start + (card_width + spacing_x) * (3 + id) as f32; // same spacing logic
-            pile_positions.insert(PileId::Foundation(id), (x, top_y));
+        // Cells, top left
+        for &id in state.cells.keys() {
+            pile_positions.insert(PileId::Cell(id), (slot_x(id), top_y));
+        }
+
+        // Foundations, top right
+        let width = variant.n_columns().max(if variant.removes_pairs() {
+            PYRAMID_ROWS
+        } else {
+            0
+        });
+        let first_foundation = width.saturating_sub(variant.n_foundations());
+        for &id in state.foundations.keys() {
+            pile_positions.insert(
+                PileId::Foundation(id),
+                (slot_x(first_foundation + id), top_y),
+            );
         }
 
         // Columns
-        for &id in game.state.columns.keys() {
-            let x = x_start + (card_width + spacing_x) * id as f32;
-            pile_positions.insert(PileId::Column(id), (x, bot_y));
+        for &id in state.columns.keys() {
+            pile_positions.insert(PileId::Column(id), (slot_x(id), bot_y));
+        }
+
+        // Pyramid or peaks, each row half a card lower than the one above
+        for &i in state.pyramid.keys() {
+            let (row, half) = variant.slot_position(i);
+            pile_positions.insert(
+                PileId::Pyramid(i),
+                (
+                    x_start + half as f32 * (card_width + spacing_x) / 2.0,
+                    bot_y + row as f32 * card_height / 2.0,
+                ),
+            );
         }
 
         Layout {
@@ -44,6 +80,7 @@ impl Layout {
             card_width,
             card_height,
             column_card_spacing,
+            waste_fan_spacing,
         }
     }
     pub fn get_position(&self, pile: &PileId) -> Option<(f32, f32)> {
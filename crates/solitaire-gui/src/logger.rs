@@ -62,13 +62,28 @@ impl GuiLogger {
             "h" | "help" => {
                 self.add(Command::Help.instructions());
             }
-            "draw" => {
+            // Spider deals its stock row by row, the same action as drawing
+            "draw" | "deal" => {
                 let msg = game
                     .handle_action(Action::Draw)
                     .unwrap_or_else(|e| e.to_string());
                 self.add(&msg);
             }
 
+            "undo" => {
+                let msg = game
+                    .handle_action(Action::Undo)
+                    .unwrap_or_else(|e| e.to_string());
+                self.add(&msg);
+            }
+
+            "redo" => {
+                let msg = game
+                    .handle_action(Action::Redo)
+                    .unwrap_or_else(|e| e.to_string());
+                self.add(&msg);
+            }
+
             "recycle" => {
                 let msg = game
                     .handle_action(Action::Recycle)
@@ -90,7 +105,7 @@ impl GuiLogger {
                     }
                 };
 
-                let from = match parse_pile(parts[2]) {
+                let from = match PileId::from_label(parts[2]) {
                     Some(p) => p,
                     None => {
                         self.add("Invalid 'from' pile");
@@ -98,7 +113,7 @@ impl GuiLogger {
                     }
                 };
 
-                let to = match parse_pile(parts[3]) {
+                let to = match PileId::from_label(parts[3]) {
                     Some(p) => p,
                     None => {
                         self.add("Invalid 'to' pile");
@@ -116,6 +131,32 @@ impl GuiLogger {
                 self.add(&msg);
             }
 
+            "pair" => {
+                if !(2..=3).contains(&parts.len()) {
+                    self.add(Command::Pair.instructions());
+                    return;
+                }
+
+                let Some(first) = PileId::from_label(parts[1]) else {
+                    self.add("Invalid first pile");
+                    return;
+                };
+
+                let second = match parts.get(2).map(|p| PileId::from_label(p)) {
+                    None => None,
+                    Some(Some(p)) => Some(p),
+                    Some(None) => {
+                        self.add("Invalid second pile");
+                        return;
+                    }
+                };
+
+                let msg = game
+                    .handle_action(Action::RemovePair { first, second })
+                    .unwrap_or_else(|e| e.to_string());
+                self.add(&msg);
+            }
+
             _ => {
                 self.add("Unknown command");
             }
@@ -123,48 +164,39 @@ impl GuiLogger {
     }
 }
 
-pub fn parse_pile(token: &str) -> Option<PileId> {
-    if token == "s" {
-        return Some(PileId::Stock);
-    }
-    if token == "w" {
-        return Some(PileId::Waste);
-    }
-
-    if let Some(rest) = token.strip_prefix("c")
-        && let Ok(n) = rest.parse::<u8>()
-    {
-        return Some(PileId::Column(n));
-    }
-
-    if let Some(rest) = token.strip_prefix("f")
-        && let Ok(n) = rest.parse::<u8>()
-    {
-        return Some(PileId::Foundation(n));
-    }
-
-    None
-}
-
 enum Command {
     Help,
     Draw,
+    Deal,
     Recycle,
     Move,
+    Pair,
+    Undo,
+    Redo,
 }
 impl Command {
     fn instructions(&self) -> String {
         match self {
             Self::Help => format!(
-                "Commands\n{}\n{}\n{}",
+                "Commands\n{}\n{}\n{}\n{}\n{}\n{}\n{}",
                 Command::Draw.instructions(),
+                Command::Deal.instructions(),
                 Command::Recycle.instructions(),
-                Command::Move.instructions()
+                Command::Move.instructions(),
+                Command::Pair.instructions(),
+                Command::Undo.instructions(),
+                Command::Redo.instructions()
             ),
             Self::Draw => "draw - draws card from stock to waste".to_string(),
+            Self::Deal => "deal - Spider: deals a card from stock onto every column".to_string(),
             Self::Recycle => "recycle - recycles cards from waste into stock".to_string(),
+            Self::Pair => {
+                "pair <a> [<b>] - Pyramid: removes the cards on <a> and <b> adding up to 13, or a King on <a> alone".to_string()
+            }
+            Self::Undo => "undo - undoes the last action".to_string(),
+            Self::Redo => "redo - replays the last undone action".to_string(),
             Self::Move => {
-                "move <n> <from> <to> - moves <n> cards from <from> to <to>\ns-stock,w-waste,fx-foundation(x=0..=3),cx-column(x=0..=6)".to_string()
+                "move <n> <from> <to> - moves <n> cards from <from> to <to>\npiles: stock, waste, cN-column, fN-foundation, cellN-free cell, pN-pyramid (N from 0)".to_string()
             }
         }
     }
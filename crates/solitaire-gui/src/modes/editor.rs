@@ -0,0 +1,316 @@
+use egui::StrokeKind;
+use serde::{Deserialize, Serialize};
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+use crate::{
+    board, card_textures::CardTextures, layout::Layout, modes::mode::Mode, ui_element::UiElement,
+};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct EditorMode {
+    #[serde(skip)]
+    pub card_textures: Option<CardTextures>,
+
+    /// Position being built
+    board: GameState,
+    /// Card picked from the palette, placed on the next clicked pile
+    #[serde(skip)]
+    selected: Option<Card>,
+    /// Whether placed cards go face down
+    face_down: bool,
+
+    /// Problems found by the last validation, `None` if not validated since the last edit
+    #[serde(skip)]
+    problems: Option<Vec<Violation>>,
+    /// Pasted position, text notation or JSON
+    #[serde(skip)]
+    input: String,
+    #[serde(skip)]
+    message: Option<String>,
+    /// Game started from the board, picked up by the app
+    #[serde(skip)]
+    started: Option<Game>,
+}
+
+impl Default for EditorMode {
+    fn default() -> Self {
+        Self {
+            card_textures: None,
+            board: empty_board(RuleSet::default()),
+            selected: None,
+            face_down: false,
+            problems: None,
+            input: String::new(),
+            message: None,
+            started: None,
+        }
+    }
+}
+
+/// Palette card size relative to board cards
+const PALETTE_SCALE: f32 = 0.5;
+
+/// Piles of the variant with no cards
+fn empty_board(rules: RuleSet) -> GameState {
+    GameState::deal(vec![], rules)
+}
+
+impl EditorMode {
+    /// Game the player asked to start, if any
+    pub fn take_started_game(&mut self) -> Option<Game> {
+        self.started.take()
+    }
+
+    fn edited(&mut self) {
+        self.problems = None;
+        self.message = None;
+    }
+    /// Copies of `card` in the deck not on the board yet, Spider plays up to eight of each
+    fn remaining(&self, card: &Card) -> usize {
+        let same = |c: &Card| c.rank() == card.rank() && c.suit() == card.suit();
+        let copies = self.board.rules.deck().iter().filter(|c| same(c)).count();
+        let mut piles = vec![PileId::Stock, PileId::Waste];
+        piles.extend(self.board.cell_ids());
+        piles.extend(self.board.foundation_ids());
+        piles.extend(self.board.column_ids());
+        piles.extend(self.board.pyramid_ids());
+        let on_board: usize = piles
+            .into_iter()
+            .filter_map(|id| self.board.pile(id).ok())
+            .map(|pile| pile.cards().iter().filter(|c| same(c)).count())
+            .sum();
+        copies.saturating_sub(on_board)
+    }
+    fn place(&mut self, card: Card, pile: PileId) {
+        let face = if self.face_down { Face::Down } else { Face::Up };
+        if let Ok(pile) = self.board.pile_mut(pile) {
+            pile.raw_insert_card(card, Side::Top, face);
+        }
+        self.selected = None;
+        self.edited();
+    }
+    fn flip(&mut self, pile: PileId, index: usize) {
+        if let Ok(pile) = self.board.pile_mut(pile)
+            && let Some(card) = pile.peek_index_mut(index)
+        {
+            card.flip();
+        }
+        self.edited();
+    }
+    /// Takes a card off the board, back into the palette
+    fn remove(&mut self, pile: PileId, index: usize) {
+        if let Ok(pile) = self.board.pile_mut(pile) {
+            pile.cards_mut().remove(index);
+        }
+        self.edited();
+    }
+
+    fn play(&mut self) {
+        match Game::from_state(self.board.clone(), self.board.rules) {
+            Ok(game) => {
+                self.started = Some(game);
+                self.problems = Some(vec![]);
+            }
+            Err(e) => self.problems = Some(e.violations),
+        }
+    }
+    fn import(&mut self) {
+        let input = std::mem::take(&mut self.input);
+        let result = if input.trim_start().starts_with('{') {
+            serde_json::from_str::<GameState>(&input).map_err(|e| e.to_string())
+        } else {
+            input.parse::<GameState>().map_err(|e| e.to_string())
+        };
+        match result {
+            Ok(state) => {
+                self.board = state;
+                self.edited();
+                self.message = Some("Position imported".to_string());
+            }
+            Err(e) => self.message = Some(format!("Cannot import: {e}")),
+        }
+    }
+}
+
+impl Mode for EditorMode {
+    fn update(&mut self) {}
+    fn render(&mut self, ctx: &egui::Context) {
+        egui::TopBottomPanel::top("editor_tools").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                for variant in enum_iterator::all::<Variant>() {
+                    if ui
+                        .selectable_label(self.board.rules.variant == variant, variant.to_string())
+                        .clicked()
+                        && self.board.rules.variant != variant
+                    {
+                        self.board = empty_board(RuleSet::for_variant(variant));
+                        self.edited();
+                    }
+                }
+                ui.separator();
+                if ui.button("Clear").clicked() {
+                    self.board = empty_board(self.board.rules);
+                    self.edited();
+                }
+                if ui
+                    .button("From new deal")
+                    .on_hover_text("Start from a freshly dealt game")
+                    .clicked()
+                {
+                    self.board = Game::with_rules(None, self.board.rules).state;
+                    self.edited();
+                }
+                ui.checkbox(&mut self.face_down, "Place face down");
+                ui.separator();
+                if ui.button("Validate").clicked() {
+                    self.problems = Some(self.board.violations());
+                }
+                if ui.button("Play").clicked() {
+                    self.play();
+                }
+                ui.separator();
+                if ui.button("Copy text").clicked() {
+                    ctx.copy_text(self.board.to_string());
+                }
+                if ui.button("Copy JSON").clicked()
+                    && let Ok(json) = serde_json::to_string_pretty(&self.board)
+                {
+                    ctx.copy_text(json);
+                }
+                ui.separator();
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.input)
+                        .desired_rows(1)
+                        .hint_text("Paste a position (text or JSON)"),
+                );
+                if ui.button("Import").clicked() {
+                    self.import();
+                }
+            });
+            if let Some(message) = &self.message {
+                ui.label(message);
+            }
+            match &self.problems {
+                Some(problems) if problems.is_empty() => {
+                    ui.colored_label(egui::Color32::GREEN, "Position is valid");
+                }
+                Some(problems) => {
+                    let problems: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+                    ui.colored_label(egui::Color32::RED, problems.join(", "));
+                }
+                None => {}
+            }
+            ui.label(
+                "Pick a card below then click a pile to place it. \
+                 Click a placed card to flip it, right-click to remove it.",
+            );
+        });
+
+        egui::TopBottomPanel::bottom("editor_palette").show(ctx, |ui| {
+            self.palette(ui);
+        });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let layout = Layout::from_state(&self.board);
+            let ui_elements = board::compute_ui_elements(&self.board, &layout);
+            board::render_ui_elements(
+                &self.board,
+                &ui_elements,
+                self.card_textures.as_ref(),
+                ui,
+                |_, _| false,
+            );
+            let response = ui.allocate_rect(ui.max_rect(), egui::Sense::click());
+            let Some(pos) = response.interact_pointer_pos() else {
+                return;
+            };
+            // Topmost element under the pointer
+            let Some(target) = ui_elements.iter().rev().find(|e| e.rect().contains(pos)) else {
+                return;
+            };
+            match (target, self.selected) {
+                (_, Some(card)) if response.clicked() => self.place(card, target.pile()),
+                (UiElement::Card { pile, index, .. }, None) if response.clicked() => {
+                    self.flip(*pile, *index)
+                }
+                (UiElement::Card { pile, index, .. }, _) if response.secondary_clicked() => {
+                    self.remove(*pile, *index)
+                }
+                _ => {}
+            }
+        });
+    }
+}
+
+// Palette
+impl EditorMode {
+    /// Every card not on the board yet, one row per suit in the deck, with the number of
+    /// copies left when the deck holds several
+    fn palette(&mut self, ui: &mut egui::Ui) {
+        let layout = Layout::from_state(&self.board);
+        let size = egui::vec2(layout.card_width, layout.card_height) * PALETTE_SCALE;
+        let spacing = 4.0;
+        let mut suits: Vec<Suit> = vec![];
+        for card in self.board.rules.deck() {
+            if !suits.contains(card.suit()) {
+                suits.push(*card.suit());
+            }
+        }
+        let (rect, _) = ui.allocate_exact_size(
+            egui::vec2(
+                13.0 * (size.x + spacing),
+                suits.len() as f32 * (size.y + spacing),
+            ),
+            egui::Sense::hover(),
+        );
+        let several = self.board.rules.deck().len() > 52;
+        for (row, suit) in suits.into_iter().enumerate() {
+            for (col, rank) in enum_iterator::all::<Rank>().enumerate() {
+                let card = Card::new(rank, suit, Face::Up);
+                let min = rect.min
+                    + egui::vec2(
+                        col as f32 * (size.x + spacing),
+                        row as f32 * (size.y + spacing),
+                    );
+                let card_rect = egui::Rect::from_min_size(min, size);
+                let remaining = self.remaining(&card);
+                if remaining == 0 {
+                    board::render_placeholder(card_rect, ui);
+                    continue;
+                }
+                board::render_card(self.card_textures.as_ref(), &card, card_rect, ui);
+                if several {
+                    ui.painter().text(
+                        card_rect.right_bottom() - egui::vec2(4.0, 2.0),
+                        egui::Align2::RIGHT_BOTTOM,
+                        format!("×{remaining}"),
+                        egui::FontId::proportional(14.0),
+                        egui::Color32::BLACK,
+                    );
+                }
+                let response = ui.interact(
+                    card_rect,
+                    ui.id().with(("palette", row, col)),
+                    egui::Sense::click(),
+                );
+                if response.clicked() {
+                    self.selected = if self.selected == Some(card) {
+                        None
+                    } else {
+                        Some(card)
+                    };
+                }
+                if self.selected == Some(card) {
+                    ui.painter().rect_stroke(
+                        card_rect,
+                        5.0,
+                        egui::Stroke::new(3.0, egui::Color32::GOLD),
+                        StrokeKind::Outside,
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,2 +1,4 @@
+pub mod editor;
 pub mod mode;
+pub mod replay;
 pub mod user_play;
@@ -0,0 +1,249 @@
+use serde::{Deserialize, Serialize};
+use solitaire_engine::prelude::*;
+
+use crate::{board, card_textures::CardTextures, layout::Layout, modes::mode::Mode};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct ReplayMode {
+    #[serde(skip)]
+    pub card_textures: Option<CardTextures>,
+
+    replay: Option<Replay>,
+    /// State before the first action, then after each one
+    #[serde(skip)]
+    states: Vec<GameState>,
+    /// Index into `states` currently shown
+    #[serde(skip)]
+    position: usize,
+    #[serde(skip)]
+    layout: Option<Layout>,
+
+    #[serde(skip)]
+    playing: bool,
+    /// Playback speed multiplier
+    speed: f64,
+    /// Time the shown position was reached, in egui time
+    #[serde(skip)]
+    last_step: f64,
+
+    /// Pasted replay text or JSON
+    #[serde(skip)]
+    input: String,
+    #[cfg(not(target_arch = "wasm32"))]
+    path: String,
+    #[serde(skip)]
+    error: Option<String>,
+}
+
+/// Longest wait between two actions during playback, in seconds at 1x
+const MAX_STEP_DELAY: f64 = 2.0;
+/// Shortest wait between two actions during playback, in seconds at 1x
+const MIN_STEP_DELAY: f64 = 0.1;
+
+impl Default for ReplayMode {
+    fn default() -> Self {
+        Self {
+            card_textures: None,
+            replay: None,
+            states: vec![],
+            position: 0,
+            layout: None,
+            playing: false,
+            speed: 1.0,
+            last_step: 0.0,
+            input: String::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            path: String::new(),
+            error: None,
+        }
+    }
+}
+
+impl ReplayMode {
+    /// Loads `replay` and rewinds to its start
+    pub fn load(&mut self, replay: Replay) {
+        self.error = None;
+        self.replay = Some(replay);
+        self.states.clear();
+        self.position = 0;
+        self.playing = false;
+        self.prepare();
+    }
+    /// Reads a replay in either the JSON or the text format
+    fn load_str(&mut self, input: &str) {
+        let result = if input.trim_start().starts_with('{') {
+            Replay::from_json(input)
+        } else {
+            Replay::from_text(input)
+        };
+        match result {
+            Ok(replay) => self.load(replay),
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_file(&mut self) {
+        match std::fs::read_to_string(&self.path) {
+            Ok(contents) => self.load_str(&contents),
+            Err(e) => self.error = Some(format!("Cannot read {}: {e}", self.path)),
+        }
+    }
+    /// Plays the loaded replay once to get every intermediate state
+    fn prepare(&mut self) {
+        let Some(replay) = &self.replay else {
+            return;
+        };
+        if !self.states.is_empty() {
+            return;
+        }
+        match replay.apply() {
+            Ok(states) => {
+                self.layout = Some(Layout::from_state(&states[0]));
+                self.states = states;
+            }
+            Err(e) => {
+                self.error = Some(e.to_string());
+                self.replay = None;
+            }
+        }
+    }
+
+    fn last_position(&self) -> usize {
+        self.states.len().saturating_sub(1)
+    }
+    fn go_to(&mut self, position: usize, now: f64) {
+        self.position = position.min(self.last_position());
+        self.last_step = now;
+    }
+    /// Seconds to wait before playing the action after the shown position
+    fn step_delay(&self) -> f64 {
+        let Some(replay) = &self.replay else {
+            return MIN_STEP_DELAY;
+        };
+        let at = |i: usize| replay.actions.get(i).map_or(0, |a| a.at_ms);
+        let gap = at(self.position).saturating_sub(if self.position == 0 {
+            0
+        } else {
+            at(self.position - 1)
+        });
+        (gap as f64 / 1000.0).clamp(MIN_STEP_DELAY, MAX_STEP_DELAY) / self.speed
+    }
+    fn step_playback(&mut self, ctx: &egui::Context) {
+        if !self.playing {
+            return;
+        }
+        let now = ctx.input(|i| i.time);
+        if self.position >= self.last_position() {
+            self.playing = false;
+        } else if now - self.last_step >= self.step_delay() {
+            self.go_to(self.position + 1, now);
+        }
+        ctx.request_repaint();
+    }
+    /// Description of the action that led to the shown position
+    fn current_action(&self) -> Option<String> {
+        let replay = self.replay.as_ref()?;
+        let action = replay.actions.get(self.position.checked_sub(1)?)?;
+        Some(action.action.to_string())
+    }
+}
+
+impl Mode for ReplayMode {
+    fn update(&mut self) {
+        self.prepare();
+    }
+    fn render(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        egui::TopBottomPanel::top("replay_load").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Replay:");
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.input)
+                        .desired_rows(1)
+                        .hint_text("Paste a replay (text or JSON)"),
+                );
+                if ui.button("Load").clicked() {
+                    let input = std::mem::take(&mut self.input);
+                    self.load_str(&input);
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    ui.separator();
+                    ui.add(egui::TextEdit::singleline(&mut self.path).hint_text("File path"));
+                    if ui.button("Open file").clicked() {
+                        self.load_file();
+                    }
+                }
+                if let Some(error) = &self.error {
+                    ui.separator();
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+            });
+        });
+
+        if !self.states.is_empty() {
+            egui::TopBottomPanel::bottom("replay_controls").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let last = self.last_position();
+                    if ui.button("⏮").on_hover_text("Start").clicked() {
+                        self.go_to(0, now);
+                    }
+                    if ui.button("⏪").on_hover_text("Step back").clicked() {
+                        self.playing = false;
+                        self.go_to(self.position.saturating_sub(1), now);
+                    }
+                    let play_label = if self.playing { "⏸" } else { "▶" };
+                    if ui.button(play_label).clicked() {
+                        if !self.playing && self.position >= last {
+                            self.position = 0;
+                        }
+                        self.playing = !self.playing;
+                        self.last_step = now;
+                    }
+                    if ui.button("⏩").on_hover_text("Step forward").clicked() {
+                        self.playing = false;
+                        self.go_to(self.position + 1, now);
+                    }
+                    if ui.button("⏭").on_hover_text("End").clicked() {
+                        self.go_to(last, now);
+                    }
+                    ui.separator();
+                    let mut position = self.position;
+                    if ui
+                        .add(egui::Slider::new(&mut position, 0..=last).text("action"))
+                        .changed()
+                    {
+                        self.go_to(position, now);
+                    }
+                    ui.separator();
+                    ui.label("Speed:");
+                    for speed in [0.5, 1.0, 2.0, 4.0] {
+                        ui.selectable_value(&mut self.speed, speed, format!("{speed}x"));
+                    }
+                    if let Some(action) = self.current_action() {
+                        ui.separator();
+                        ui.label(action);
+                    }
+                });
+            });
+        }
+
+        self.step_playback(ctx);
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let (Some(state), Some(layout)) = (self.states.get(self.position), &self.layout) else {
+                ui.label("Load a replay to watch it");
+                return;
+            };
+            let ui_elements = board::compute_ui_elements(state, layout);
+            board::render_ui_elements(
+                state,
+                &ui_elements,
+                self.card_textures.as_ref(),
+                ui,
+                |_, _| false,
+            );
+        });
+    }
+}
@@ -1,13 +1,18 @@
+use std::collections::VecDeque;
+
 use egui::StrokeKind;
 use serde::{Deserialize, Serialize};
 use solitaire_core::{
-    card::{Card, Face, Suit},
+    card::Card,
+    deal::DealAlgorithm,
     pile::{PileId, Side},
+    pyramid::is_pair,
+    rules::{BuildOrder, EmptyColumnRule, FoundationSuits, PassLimit, RuleSet, SuitCount, Variant},
 };
 use solitaire_engine::prelude::*;
 
 use crate::{
-    card_textures::CardTextures, layout::Layout, logger::GuiLogger, modes::mode::Mode,
+    board, card_textures::CardTextures, layout::Layout, logger::GuiLogger, modes::mode::Mode,
     ui_element::UiElement,
 };
 
@@ -29,8 +34,43 @@ pub struct UserPlayMode {
 
     #[serde(skip)]
     layout: Layout,
+
+    /// Rules used for the next new game
+    rules: RuleSet,
+
+    #[serde(skip)]
+    hint: Option<Hint>,
+    /// Moves left to play out by the auto-finish animation
+    #[serde(skip)]
+    auto_finish: VecDeque<Action>,
+    #[serde(skip)]
+    last_auto_move: f64,
+    /// Whether new games automatically play safe cards to the foundations
+    auto_play: bool,
+    /// Scoring used for the next new game
+    scoring: ScoringMode,
+    /// Shuffle used for the next new game
+    deal: DealAlgorithm,
+
+    stats: PlayerStats,
+    /// How far the current game got, as counted in `stats`
+    progress: GameProgress,
+    #[serde(skip)]
+    stats_open: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+enum GameProgress {
+    /// No move made yet, not counted
+    #[default]
+    Dealt,
+    Started,
+    Won,
 }
 
+/// Seconds between two auto-finish moves
+const AUTO_FINISH_DELAY: f64 = 0.08;
+
 #[derive(Debug)]
 pub struct Dragging {
     from: PileId,
@@ -50,6 +90,16 @@ impl Default for UserPlayMode {
             #[cfg(debug_assertions)]
             debug_mode: false,
             layout: Layout::new(&game),
+            rules: RuleSet::default(),
+            hint: None,
+            auto_finish: VecDeque::new(),
+            last_auto_move: 0.0,
+            auto_play: false,
+            scoring: ScoringMode::default(),
+            deal: DealAlgorithm::default(),
+            stats: PlayerStats::default(),
+            progress: GameProgress::default(),
+            stats_open: false,
         };
         let ui_elements = app.compute_ui_elements();
         app.ui_elements = ui_elements;
@@ -59,20 +109,75 @@ impl Default for UserPlayMode {
 
 impl UserPlayMode {
     pub fn new_game(&mut self) {
-        self.game = Game::new(None);
+        // A cumulative Vegas bankroll carries over whatever the last game ended with
+        let previous = self.game.score();
+        let bankroll = match previous.mode {
+            ScoringMode::Vegas { cumulative: true } => previous.points,
+            _ => 0,
+        };
+        self.leave_game();
+        self.game = Game::with_deal(None, self.rules, self.deal)
+            .expect("random seeds are drawn from the deal's seeds");
+        self.game.set_auto_play(self.auto_play);
+        self.game.set_scoring(self.scoring, bankroll);
         self.logger.clear();
+        self.hint = None;
+        self.auto_finish.clear();
+    }
+    /// Plays `game` from now on, e.g. a position set up in the editor
+    pub fn load_game(&mut self, game: Game) {
+        self.leave_game();
+        self.game = game;
+        self.game.set_auto_play(self.auto_play);
+        self.game.set_scoring(self.scoring, 0);
+        self.logger.clear();
+        self.hint = None;
+        self.auto_finish.clear();
     }
     pub fn reset(&mut self) {
-        self.game.reset();
+        self.leave_game();
         self.logger.clear();
+        if let Err(e) = self.game.reset() {
+            self.logger.add(e.to_string());
+        }
+        self.hint = None;
+        self.auto_finish.clear();
+    }
+    pub fn start_auto_finish(&mut self) {
+        if let Some(actions) = self.game.state.auto_complete_actions() {
+            self.auto_finish = actions.into();
+        }
+    }
+    /// Plays the next auto-finish move once enough time has passed
+    fn step_auto_finish(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        if self.auto_finish.is_empty() || now - self.last_auto_move < AUTO_FINISH_DELAY {
+            return;
+        }
+        self.last_auto_move = now;
+        if let Some(action) = self.auto_finish.pop_front() {
+            self.execute_action(action);
+        }
+    }
+    pub fn show_hint(&mut self) {
+        self.hint = self.game.hint();
+        match &self.hint {
+            Some(hint) => self.logger.add(format!("Hint: {}", hint.reason)),
+            None => self.logger.add("Hint: no useful move left"),
+        }
     }
     pub fn undo(&mut self) {
+        self.auto_finish.clear();
         self.execute_action(Action::Undo);
     }
+    pub fn redo(&mut self) {
+        self.execute_action(Action::Redo);
+    }
 }
 
 impl Mode for UserPlayMode {
     fn render(&mut self, ctx: &egui::Context) {
+        self.update_clock(ctx);
         egui::TopBottomPanel::top("aa").show(ctx, |ui| {
             ui.horizontal(|ui| {
                 if ui.button("New Game").clicked() {
@@ -81,12 +186,61 @@ impl Mode for UserPlayMode {
                 if ui.button("Reset").clicked() {
                     self.reset();
                 }
-                if ui.button("Undo").clicked() {
+                if ui
+                    .add_enabled(self.game.can_undo(), egui::Button::new("Undo"))
+                    .on_hover_text("Ctrl+Z")
+                    .clicked()
+                {
                     self.undo();
                 }
+                if ui
+                    .add_enabled(self.game.can_redo(), egui::Button::new("Redo"))
+                    .on_hover_text("Ctrl+Y")
+                    .clicked()
+                {
+                    self.redo();
+                }
+                if ui.button("Hint").clicked() {
+                    self.show_hint();
+                }
+                if self.auto_finish.is_empty()
+                    && self.game.state.can_auto_complete()
+                    && ui.button("Auto-finish").clicked()
+                {
+                    self.start_auto_finish();
+                }
+                ui.separator();
+                self.rules_menu(ui);
+                if ui.button("Statistics").clicked() {
+                    self.stats_open = !self.stats_open;
+                }
+                if ui
+                    .button("Copy replay")
+                    .on_hover_text("Copy this game's replay, to paste in the Replay mode")
+                    .clicked()
+                {
+                    ctx.copy_text(Replay::from_game(&self.game).to_text());
+                }
+                if ui
+                    .checkbox(&mut self.auto_play, "Auto-play")
+                    .on_hover_text("Move safe cards to the foundations automatically")
+                    .changed()
+                {
+                    self.game.set_auto_play(self.auto_play);
+                }
                 ui.separator();
                 ui.label("Moves: ");
                 ui.label(format!("{}", self.game.n_moves()));
+                ui.separator();
+                ui.label("Score: ");
+                ui.label(self.game.timed_score().to_string());
+                ui.separator();
+                ui.label("Time: ");
+                ui.label(format_duration(self.game.elapsed()));
+                if let Some(hint) = &self.hint {
+                    ui.separator();
+                    ui.label(&hint.reason);
+                }
             });
         });
         #[cfg(debug_assertions)]
@@ -100,18 +254,24 @@ impl Mode for UserPlayMode {
             }
         }
 
+        self.handle_shortcuts(ctx);
+        self.step_auto_finish(ctx);
         self.ui_elements = self.compute_ui_elements();
 
         egui::CentralPanel::default().show(ctx, |ui| {
             self.render_ui_elements(ui);
             self.handle_interactions(ui);
         });
+        self.update_stats();
+        self.stats_window(ctx);
 
         if self.game.state.is_won() {
             egui::Modal::new(egui::Id::new("won_modal")).show(ctx, |ui| {
                 ui.heading("You WON");
                 ui.label("Congratulation");
                 ui.label(format!("Moves: {}", self.game.n_moves()));
+                ui.label(format!("Score: {}", self.game.timed_score()));
+                ui.label(format!("Time: {}", format_duration(self.game.elapsed())));
                 ui.add_space(10.0);
                 ui.horizontal(|ui| {
                     if ui.button("New Game").clicked() {
@@ -124,25 +284,249 @@ impl Mode for UserPlayMode {
             });
         }
     }
-    fn update(&mut self) {}
+    fn update(&mut self) {
+        // Piles move with the variant, e.g. after a new game or restoring a saved one
+        self.layout = Layout::new(&self.game);
+    }
+}
+
+// Statistics
+impl UserPlayMode {
+    /// Counts the current game as started or won as soon as it becomes so
+    fn update_stats(&mut self) {
+        let rules = self.game.state.rules;
+        if self.progress == GameProgress::Dealt && self.game.n_moves() > 0 {
+            self.stats.game_started(&rules);
+            self.progress = GameProgress::Started;
+        }
+        if self.progress == GameProgress::Started && self.game.state.is_won() {
+            self.stats.game_won(
+                &rules,
+                self.game.n_moves(),
+                self.game.elapsed(),
+                self.game.timed_score().points,
+            );
+            self.progress = GameProgress::Won;
+        }
+    }
+    /// Counts the current game as abandoned if it was started but not won
+    fn leave_game(&mut self) {
+        if self.progress == GameProgress::Started {
+            self.stats.game_abandoned(&self.game.state.rules);
+        }
+        self.progress = GameProgress::Dealt;
+    }
+    fn stats_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.stats_open;
+        egui::Window::new("Statistics")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                let (started, won) = self.stats.totals();
+                ui.label(format!("Games won: {won} of {started}"));
+                ui.separator();
+                if self.stats.variants().is_empty() {
+                    ui.label("No games played yet");
+                }
+                for variant in self.stats.variants() {
+                    ui.strong(variant.rules.to_string());
+                    egui::Grid::new(format!("{:?}", variant.rules))
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            let rows = [
+                                ("Played", variant.started.to_string()),
+                                ("Won", variant.won.to_string()),
+                                ("Abandoned", variant.abandoned.to_string()),
+                                ("Win rate", format!("{:.0}%", variant.win_percentage())),
+                                ("Current streak", variant.current_streak.to_string()),
+                                ("Longest streak", variant.longest_streak.to_string()),
+                                (
+                                    "Fewest moves",
+                                    variant
+                                        .fewest_moves
+                                        .map_or("-".to_string(), |m| m.to_string()),
+                                ),
+                                (
+                                    "Fastest",
+                                    variant.fastest.map_or("-".to_string(), format_duration),
+                                ),
+                                (
+                                    "Best score",
+                                    variant
+                                        .best_score
+                                        .map_or("-".to_string(), |s| s.to_string()),
+                                ),
+                            ];
+                            for (name, value) in rows {
+                                ui.label(name);
+                                ui.label(value);
+                                ui.end_row();
+                            }
+                        });
+                    ui.separator();
+                }
+                if ui.button("Reset statistics").clicked() {
+                    self.stats.reset();
+                }
+            });
+        self.stats_open = open;
+    }
+}
+
+// Clock
+impl UserPlayMode {
+    /// Play time only counts while the window is focused and no modal is open
+    fn update_clock(&mut self, ctx: &egui::Context) {
+        let focused = ctx.input(|i| i.focused);
+        let modal_open = ctx.memory(|m| m.top_modal_layer().is_some());
+        if focused && !modal_open {
+            self.game.resume();
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+        } else {
+            self.game.pause();
+        }
+    }
+}
+
+fn format_duration(duration: std::time::Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+// Rules
+impl UserPlayMode {
+    fn rules_menu(&mut self, ui: &mut egui::Ui) {
+        ui.menu_button("Rules", |ui| {
+            ui.label("Applies to the next new game");
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Game:");
+                for variant in enum_iterator::all::<Variant>() {
+                    // Each game starts from its usual rules
+                    if ui
+                        .selectable_label(self.rules.variant == variant, variant.to_string())
+                        .clicked()
+                        && self.rules.variant != variant
+                    {
+                        self.rules = RuleSet::for_variant(variant);
+                    }
+                }
+            });
+            let rules = &mut self.rules;
+            if rules.variant == Variant::Spider {
+                ui.horizontal(|ui| {
+                    ui.label("Suits:");
+                    for suits in enum_iterator::all::<SuitCount>() {
+                        ui.selectable_value(&mut rules.suits, suits, suits.to_string());
+                    }
+                });
+            }
+            if rules.variant.builds_on_waste() {
+                ui.checkbox(&mut rules.wrap, "King and Ace follow each other");
+            }
+            if rules.variant.has_waste() {
+                ui.horizontal(|ui| {
+                    ui.label("Draw:");
+                    for n in [1, 3] {
+                        ui.selectable_value(&mut rules.draw_count, n, format!("{n}"));
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Passes:");
+                    for limit in [
+                        PassLimit::Unlimited,
+                        PassLimit::Passes(3),
+                        PassLimit::Passes(1),
+                    ] {
+                        ui.selectable_value(&mut rules.pass_limit, limit, limit.to_string());
+                    }
+                });
+            }
+            ui.horizontal(|ui| {
+                ui.label("Build:");
+                for order in [
+                    BuildOrder::AlternateColor,
+                    BuildOrder::SameSuit,
+                    BuildOrder::AnySuit,
+                ] {
+                    ui.selectable_value(&mut rules.build_order, order, order.to_string());
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Empty column:");
+                for rule in [
+                    EmptyColumnRule::KingOnly,
+                    EmptyColumnRule::Any,
+                    EmptyColumnRule::None,
+                ] {
+                    ui.selectable_value(&mut rules.empty_column, rule, rule.to_string());
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Foundation suits:");
+                for suits in [FoundationSuits::Any, FoundationSuits::Fixed] {
+                    ui.selectable_value(&mut rules.foundation_suits, suits, suits.to_string());
+                }
+            });
+            ui.checkbox(
+                &mut rules.foundation_to_tableau,
+                "Allow moves from foundation to tableau",
+            );
+            ui.horizontal(|ui| {
+                ui.label("Scoring:");
+                for mode in [
+                    ScoringMode::Standard,
+                    ScoringMode::Vegas { cumulative: false },
+                    ScoringMode::Vegas { cumulative: true },
+                ] {
+                    ui.selectable_value(&mut self.scoring, mode, mode.to_string());
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Deal:");
+                for deal in [DealAlgorithm::Portable, DealAlgorithm::Microsoft] {
+                    ui.selectable_value(&mut self.deal, deal, deal.to_string());
+                }
+            });
+            ui.separator();
+            if ui.button("Reset to default").clicked() {
+                self.rules = RuleSet::for_variant(self.rules.variant);
+                self.scoring = ScoringMode::default();
+                self.deal = DealAlgorithm::default();
+            }
+        });
+    }
 }
 
 // Interactions
 impl UserPlayMode {
     fn execute_action(&mut self, action: Action) {
+        self.hint = None;
         match self.game.handle_action(action) {
             Ok(msg) => self.logger.add(format!("Success: {}", msg)),
             Err(e) => self.logger.add(format!("Failed: {:?}", e)),
         }
     }
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        let redo_shift_z = egui::Modifiers::COMMAND | egui::Modifiers::SHIFT;
+        if ctx.input_mut(|i| {
+            i.consume_key(egui::Modifiers::COMMAND, egui::Key::Y)
+                || i.consume_key(redo_shift_z, egui::Key::Z)
+        }) {
+            self.redo();
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z)) {
+            self.undo();
+        }
+    }
     fn handle_interactions(&mut self, ui: &mut egui::Ui) {
         // Handle ongoing drag
         if let Some(dragging) = &self.dragging {
             let pointer_released = ui.input(|i| i.pointer.any_released());
             if pointer_released {
                 if let Some(pos) = ui.input(|i| i.pointer.interact_pos()) {
-                    // Find which pile (if any) the mouse is over
-                    let target_pile = self.ui_elements.iter().find_map(|e| match e {
+                    // Find which pile (if any) the mouse is over, topmost first
+                    let target_pile = self.ui_elements.iter().rev().find_map(|e| match e {
                         UiElement::Card { pile, rect, .. }
                         | UiElement::EmptyPile { pile, rect, .. } => {
                             if rect.contains(pos) {
@@ -154,10 +538,18 @@ impl UserPlayMode {
                     });
 
                     if let Some(to_pile) = target_pile {
-                        let action = Action::Move {
-                            from: dragging.from,
-                            to: to_pile,
-                            num_cards: dragging.cards.len(),
+                        // Pyramid: dropping a card on another pairs them up
+                        let action = if self.game.state.rules.variant.removes_pairs() {
+                            Action::RemovePair {
+                                first: dragging.from,
+                                second: Some(to_pile),
+                            }
+                        } else {
+                            Action::Move {
+                                from: dragging.from,
+                                to: to_pile,
+                                num_cards: dragging.cards.len(),
+                            }
                         };
 
                         self.execute_action(action);
@@ -198,6 +590,46 @@ impl UserPlayMode {
                         actions_to_execute.push(Action::Draw);
                     }
 
+                    // Pyramid: clicking a King removes it on its own
+                    if response.clicked()
+                        && self.game.state.rules.variant.removes_pairs()
+                        && pile != &PileId::Stock
+                        && is_pair(card, None)
+                    {
+                        actions_to_execute.push(Action::RemovePair {
+                            first: *pile,
+                            second: None,
+                        });
+                    }
+
+                    let is_top = self
+                        .game
+                        .state
+                        .pile(*pile)
+                        .is_ok_and(|p| *index + 1 == p.len());
+
+                    // Golf and TriPeaks: clicking a free card plays it onto the waste
+                    if response.clicked()
+                        && is_top
+                        && self.game.state.rules.variant.builds_on_waste()
+                        && matches!(pile, PileId::Column(_) | PileId::Pyramid(_))
+                    {
+                        actions_to_execute.push(Action::Move {
+                            num_cards: 1,
+                            from: *pile,
+                            to: PileId::Waste,
+                        });
+                    }
+
+                    // Double-click sends a top card to its foundation
+                    if response.double_clicked()
+                        && is_top
+                        && matches!(pile, PileId::Waste | PileId::Column(_) | PileId::Cell(_))
+                        && let Some(action) = self.game.state.foundation_move_for(*pile)
+                    {
+                        actions_to_execute.push(action);
+                    }
+
                     // Drag start
                     if response.drag_started() {
                         let mut cards = match pile {
@@ -244,49 +676,21 @@ impl UserPlayMode {
 /// Render
 impl UserPlayMode {
     fn render_ui_elements(&self, ui: &mut egui::Ui) {
-        for ui_element in &self.ui_elements {
-            match ui_element {
-                UiElement::Card {
-                    card, rect, pile, ..
-                } => {
-                    if let Some(dragging) = &self.dragging {
-                        // Skip any card that's part of the dragging stack from this pile
-                        if *pile == dragging.from && dragging.cards.contains(card) {
-                            continue;
-                        }
-                    }
-                    self.render_card(card, *rect, ui);
-                }
-                UiElement::EmptyPile { pile, rect, .. } => {
-                    Self::render_placeholder(*rect, ui);
-                    if let PileId::Foundation(i) = pile
-                        && let Some(pile) = self.game.state.foundations.get(i)
-                    {
-                        self.render_template(pile.suit(), *rect, ui);
-                    }
-                }
-            }
+        board::render_ui_elements(
+            &self.game.state,
+            &self.ui_elements,
+            self.card_textures.as_ref(),
+            ui,
+            |pile, card| {
+                // Skip any card that's part of the dragging stack from this pile
+                self.dragging
+                    .as_ref()
+                    .is_some_and(|d| pile == d.from && d.cards.contains(card))
+            },
+        );
+        if let Some(hint) = &self.hint {
+            self.render_hint(hint, ui);
         }
-        // for ui_element in &self.ui_elements {
-        //     match ui_element {
-        //         UiElement::Card {
-        //             card, rect, pile, ..
-        //         } => {
-        //             let rect = rect.translate(offset.to_vec2());
-        //             self.render_card(card, rect, ui);
-        //         }
-        //         UiElement::EmptyPile { pile, rect, .. } => {
-        //             let rect = rect.translate(offset.to_vec2());
-        //             Self::render_placeholder(rect, ui);
-
-        //             if let PileId::Foundation(i) = pile
-        //                 && let Some(pile) = self.game.foundations.get(i)
-        //             {
-        //                 self.render_template(pile.suit(), rect, ui);
-        //             }
-        //         }
-        //     }
-        // }
         // If dragging, render the dragging cards at mouse cursor
         if let Some(dragging) = &self.dragging
             && let Some(mouse_pos) = ui.input(|i| i.pointer.hover_pos())
@@ -302,146 +706,57 @@ impl UserPlayMode {
                     pos,
                     egui::Vec2::new(self.layout.card_width, self.layout.card_height),
                 );
-                self.render_card(card, rect, ui);
+                board::render_card(self.card_textures.as_ref(), card, rect, ui);
             }
         }
     }
-    fn render_card(&self, card: &Card, rect: egui::Rect, ui: &mut egui::Ui) {
-        if let Some(card_textures) = &self.card_textures {
-            let tex_opt = match card.face() {
-                Face::Down => Some(card_textures.get_back()),
-                Face::Up => card_textures.get(*card.suit(), *card.rank()),
+    /// Outlines the cards a hint moves and where they go
+    fn render_hint(&self, hint: &Hint, ui: &mut egui::Ui) {
+        let (from, num_cards, to) = match hint.action {
+            Action::Draw | Action::Recycle => (PileId::Stock, 1, None),
+            Action::Move {
+                num_cards,
+                from,
+                to,
+            } => (from, num_cards, Some(to)),
+            Action::RemovePair { first, second } => (first, 1, second),
+            _ => return,
+        };
+        let stroke = egui::Stroke::new(3.0, egui::Color32::GOLD);
+
+        // Source: top `num_cards` of the pile, or its placeholder if empty
+        let from_len = self.game.state.pile(from).map_or(0, |p| p.len());
+        for ui_element in &self.ui_elements {
+            let highlighted = match ui_element {
+                UiElement::Card { pile, index, .. } => {
+                    *pile == from && *index + num_cards >= from_len
+                }
+                UiElement::EmptyPile { pile, .. } => *pile == from && from_len == 0,
             };
-            if let Some(tex) = tex_opt {
-                ui.put(
-                    rect,
-                    egui::Image::new((tex.id(), rect.size())).fit_to_exact_size(rect.size()),
-                );
+            if highlighted {
+                ui.painter()
+                    .rect_stroke(ui_element.rect(), 5.0, stroke, StrokeKind::Outside);
             }
         }
-    }
-    fn render_template(&self, suit: &Suit, rect: egui::Rect, ui: &mut egui::Ui) {
-        if let Some(card_textures) = &self.card_textures
-            && let Some(tex) = card_textures.get_template(*suit)
+
+        // Destination: the top of the pile (last element pushed for it)
+        if let Some(to) = to
+            && let Some(ui_element) = self.ui_elements.iter().rev().find(|e| e.pile() == to)
         {
-            ui.put(
-                rect,
-                egui::Image::new((tex.id(), rect.size())).fit_to_exact_size(rect.size()),
+            ui.painter().rect_stroke(
+                ui_element.rect(),
+                5.0,
+                egui::Stroke::new(3.0, egui::Color32::LIGHT_GREEN),
+                StrokeKind::Outside,
             );
         }
     }
-    fn render_placeholder(rect: egui::Rect, ui: &mut egui::Ui) {
-        ui.painter().rect_stroke(
-            rect,
-            5.0,
-            egui::Stroke::new(1.0, egui::Color32::LIGHT_GRAY),
-            StrokeKind::Inside,
-        );
-    }
 }
 
-enum PileLayout {
-    Overlap,
-    Vertical { spacing: f32 },
-}
 // Ui elements
 impl UserPlayMode {
-    /// Create UiElement entries for a pile
-    fn push_pile_elements_generic(
-        &self,
-        ui_elements: &mut Vec<UiElement>,
-        pile_id: PileId,
-        cards: &[Card], // ordered bottom → top
-        base_pos: (f32, f32),
-        layout: PileLayout,
-    ) {
-        let base_rect = self.card_rect(base_pos);
-
-        // Always push empty pile entry first
-        ui_elements.push(UiElement::empty_pile(pile_id, base_rect, cards.is_empty()));
-
-        match layout {
-            PileLayout::Overlap => {
-                for (i, card) in cards.iter().enumerate() {
-                    let is_top = i == cards.len().saturating_sub(1);
-                    let interactible = is_top;
-                    ui_elements.push(UiElement::card(*card, pile_id, i, base_rect, interactible));
-                }
-            }
-
-            PileLayout::Vertical { spacing } => {
-                for (i, card) in cards.iter().enumerate() {
-                    let pos = (base_pos.0, base_pos.1 + spacing * i as f32);
-                    let rect = self.card_rect(pos);
-                    let interactible = card.face() == &Face::Up;
-                    ui_elements.push(UiElement::card(*card, pile_id, i, rect, interactible));
-                }
-            }
-        }
-    }
-
-    /// Compute all ui elements (cards and empty piles)
     fn compute_ui_elements(&self) -> Vec<UiElement> {
-        let mut ui_elements: Vec<UiElement> = Vec::new();
-
-        // Stock, Waste
-        for pile_id in [PileId::Stock, PileId::Waste] {
-            if let Some(pos) = self.layout.get_position(&pile_id)
-                && let Ok(pile) = self.game.state.pile(pile_id)
-            {
-                let cards = pile.peek_cards(pile.len(), Side::Bottom);
-                self.push_pile_elements_generic(
-                    &mut ui_elements,
-                    pile_id,
-                    &cards,
-                    pos,
-                    PileLayout::Overlap,
-                );
-            }
-        }
-
-        // Foundations
-        for pile_id in self.game.state.foundation_ids() {
-            if let Some(pos) = self.layout.get_position(&pile_id)
-                && let Ok(pile_ref) = self.game.state.pile(pile_id)
-            {
-                let cards = pile_ref.peek_cards(pile_ref.len(), Side::Bottom);
-                self.push_pile_elements_generic(
-                    &mut ui_elements,
-                    pile_id,
-                    &cards,
-                    pos,
-                    PileLayout::Overlap,
-                );
-            }
-        }
-
-        // Columns
-        for pile_id in self.game.state.column_ids() {
-            if let Some(pos) = self.layout.get_position(&pile_id)
-                && let Ok(pile_ref) = self.game.state.pile(pile_id)
-            {
-                let cards = pile_ref.peek_all(Side::Bottom);
-                self.push_pile_elements_generic(
-                    &mut ui_elements,
-                    pile_id,
-                    &cards,
-                    pos,
-                    PileLayout::Vertical {
-                        spacing: self.layout.column_card_spacing,
-                    },
-                );
-            }
-        }
-
-        ui_elements
-    }
-
-    fn card_rect(&self, position: (f32, f32)) -> egui::Rect {
-        egui::Rect::from_min_size(
-            egui::Pos2::from(position),
-            egui::vec2(self.layout.card_width, self.layout.card_height),
-        )
+        board::compute_ui_elements(&self.game.state, &self.layout)
     }
 }
 
@@ -455,6 +770,13 @@ impl UserPlayMode {
                     self.logger.render(ui, &mut self.game);
                 });
             ui.label(format!("Dragging: {:?}", self.dragging));
+            if ui
+                .button("Copy position")
+                .on_hover_text("Copy the board in text notation")
+                .clicked()
+            {
+                ctx.copy_text(self.game.state.to_string());
+            }
             self.display_piles(ui);
             self.display_interactble_elements(ui);
             self.display_actions_results(ui);
@@ -466,13 +788,19 @@ impl UserPlayMode {
             if let PileId::Foundation(i) = pile_id
                 && let Some(pile) = self.game.state.foundations.get(&i)
             {
-                ui.label(format!(" {}", pile.suit()));
+                match pile.suit() {
+                    Some(suit) => ui.label(format!(" {suit}")),
+                    None => ui.label(" unclaimed"),
+                };
             }
         });
         if let Ok(pile) = self.game.state.pile(pile_id) {
-            for card in pile.peek_cards(pile.len(), Side::Bottom) {
-                ui.label(format!("{card:?}"));
-            }
+            let cards: Vec<String> = pile
+                .peek_cards(pile.len(), Side::Bottom)
+                .iter()
+                .map(|card| card.to_string())
+                .collect();
+            ui.label(cards.join(" "));
         }
         ui.separator();
     }
@@ -485,12 +813,18 @@ impl UserPlayMode {
                 .show(ui, |ui| {
                     self.display_pile(PileId::Stock, ui);
                     self.display_pile(PileId::Waste, ui);
+                    for id in self.game.state.cell_ids() {
+                        self.display_pile(id, ui);
+                    }
                     for id in self.game.state.foundation_ids() {
                         self.display_pile(id, ui);
                     }
                     for id in self.game.state.column_ids() {
                         self.display_pile(id, ui);
                     }
+                    for id in self.game.state.pyramid_ids() {
+                        self.display_pile(id, ui);
+                    }
                 });
             ui.separator();
         });
@@ -510,6 +844,10 @@ impl UserPlayMode {
         ui.separator();
         ui.collapsing("ActionsResults", |ui| {
             let actions = self.game.actions_results().data();
+            ui.label(format!(
+                "Waste fan: {} (top card playable)",
+                self.game.actions_results().fanned()
+            ));
 
             // Separate actions into Ok and Err
             let (ok_actions, err_actions): (Vec<_>, Vec<_>) = actions
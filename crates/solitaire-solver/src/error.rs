@@ -0,0 +1,22 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use solitaire_core::rules::Variant;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SolverError {
+    /// Only Klondike positions are searched, other variants have piles the solver ignores
+    UnsupportedVariant(Variant),
+}
+
+impl std::error::Error for SolverError {}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolverError::UnsupportedVariant(variant) => {
+                write!(f, "Cannot solve {variant}: only Klondike is supported")
+            }
+        }
+    }
+}
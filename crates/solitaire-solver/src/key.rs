@@ -0,0 +1,50 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+/// Encoding of a position, equal for positions that only differ in column order
+/// or in which foundation holds which suit.
+///
+/// The whole encoding is kept rather than a hash of it, so two positions never
+/// share a key and `Unsolvable` is exact.
+pub fn state_key(state: &GameState) -> Vec<u8> {
+    let mut key = vec![];
+
+    // Columns are interchangeable, so sort them
+    let mut columns: Vec<Vec<u8>> = state
+        .columns
+        .values()
+        .map(|c| c.cards().iter().map(encode_card).collect())
+        .collect();
+    columns.sort_unstable();
+    for column in columns {
+        key.push(column.len() as u8);
+        key.extend(column);
+    }
+
+    // Foundations only matter by how far each suit got
+    key.extend(
+        [Suit::Heart, Suit::Club, Suit::Diamond, Suit::Spade]
+            .map(|suit| state.foundation_rank(suit).map_or(0, |r| r as u8)),
+    );
+
+    for pile in [&state.stock as &dyn PileBehavior, &state.waste] {
+        key.push(pile.len() as u8);
+        key.extend(pile.cards().iter().map(encode_card));
+    }
+
+    // Recycles only change the outcome when they are limited
+    if state.remaining_passes().is_some() {
+        key.extend(state.recycles.to_le_bytes());
+    }
+
+    key
+}
+
+/// Packs a card into one byte: suit, rank and face
+fn encode_card(card: &Card) -> u8 {
+    let face = match card.face() {
+        Face::Up => 0x40,
+        Face::Down => 0,
+    };
+    (*card.suit() as u8) << 4 | *card.rank() as u8 | face
+}
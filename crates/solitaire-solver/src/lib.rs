@@ -0,0 +1,11 @@
+pub mod error;
+pub mod key;
+pub mod moves;
+pub mod solver;
+
+pub mod prelude {
+    pub use crate::error::*;
+    pub use crate::key::*;
+    pub use crate::moves::*;
+    pub use crate::solver::*;
+}
@@ -0,0 +1,148 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+/// Legal actions from `state`, most promising first.
+///
+/// If a card can safely go to a foundation that move is returned alone, since
+/// playing it first never loses a win.
+pub fn candidate_moves(state: &GameState) -> Vec<Action> {
+    let mut to_foundation = vec![];
+    let mut revealing = vec![];
+    let mut from_waste = vec![];
+    let mut other = vec![];
+    let mut stock = vec![];
+    let mut from_foundation = vec![];
+
+    let foundation_ids = state.foundation_ids();
+    let column_ids = state.column_ids();
+
+    // Waste and column tops to foundations
+    let mut tops = vec![PileId::Waste];
+    tops.extend(column_ids.iter().copied());
+    for from in tops {
+        let Some(card) = state.pile(from).ok().and_then(|p| p.peek(Side::Top)) else {
+            continue;
+        };
+        // Every foundation accepting it gives the same position
+        if let Some(&to) = foundation_ids.iter().find(|&&to| state.accepts(to, card)) {
+            let action = Action::Move {
+                num_cards: 1,
+                from,
+                to,
+            };
+            if state.is_safe_to_foundation(&card) {
+                return vec![action];
+            }
+            to_foundation.push(action);
+        }
+    }
+
+    // Columns to columns
+    for &from in &column_ids {
+        let Ok(pile) = state.pile(from) else {
+            continue;
+        };
+        let len = pile.len();
+        for n in 1..=pile.max_take_count() {
+            let Some(card) = pile.peek_index(len - n) else {
+                continue;
+            };
+            let reveals =
+                n == len || pile.peek_index(len - n - 1).map(|c| *c.face()) == Some(Face::Down);
+            // Shifting a run sideways only opens up the card left behind
+            if !reveals && !frees_a_play(state, from, n) {
+                continue;
+            }
+            for &to in &column_ids {
+                if to == from || !state.accepts(to, card) {
+                    continue;
+                }
+                // Shuffling a whole column into an empty one changes nothing
+                if n == len && state.pile(to).is_ok_and(|p| p.is_empty()) {
+                    continue;
+                }
+                let action = Action::Move {
+                    num_cards: n,
+                    from,
+                    to,
+                };
+                if reveals {
+                    revealing.push(action);
+                } else {
+                    other.push(action);
+                }
+            }
+        }
+    }
+
+    // Waste to columns
+    if let Some(card) = state.waste.peek(Side::Top) {
+        for &to in &column_ids {
+            if state.accepts(to, card) {
+                from_waste.push(Action::Move {
+                    num_cards: 1,
+                    from: PileId::Waste,
+                    to,
+                });
+            }
+        }
+    }
+
+    // Stock
+    if !state.stock.is_empty() {
+        stock.push(Action::Draw);
+    } else if !state.waste.is_empty() && state.remaining_passes() != Some(0) {
+        stock.push(Action::Recycle);
+    }
+
+    // Foundations back to columns
+    if state.rules.foundation_to_tableau {
+        for &from in &foundation_ids {
+            let Some(card) = state.pile(from).ok().and_then(|p| p.peek(Side::Top)) else {
+                continue;
+            };
+            for &to in &column_ids {
+                if state.accepts(to, card) {
+                    from_foundation.push(Action::Move {
+                        num_cards: 1,
+                        from,
+                        to,
+                    });
+                }
+            }
+        }
+    }
+
+    let mut moves = to_foundation;
+    moves.extend(revealing);
+    moves.extend(from_waste);
+    moves.extend(other);
+    moves.extend(stock);
+    moves.extend(from_foundation);
+    moves
+}
+
+/// Whether taking the top `n` cards off column `from` lets the card left on top go to
+/// a foundation or take the waste card
+fn frees_a_play(state: &GameState, from: PileId, n: usize) -> bool {
+    let PileId::Column(i) = from else {
+        return false;
+    };
+    let Some(mut column) = state.columns.get(&i).cloned() else {
+        return false;
+    };
+    if column.take_cards(n, Side::Top).is_err() {
+        return false;
+    }
+    let Some(exposed) = column.peek(Side::Top) else {
+        return false;
+    };
+    state
+        .foundation_ids()
+        .into_iter()
+        .any(|to| state.accepts(to, exposed))
+        || state
+            .waste
+            .peek(Side::Top)
+            .is_some_and(|card| column.insert_card(card, Side::Top, Face::Up).is_ok())
+}
@@ -0,0 +1,103 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use solitaire_core::rules::{RuleSet, Variant};
+use solitaire_engine::prelude::*;
+
+use crate::{error::SolverError, key::state_key, moves::candidate_moves};
+
+/// Outcome of a search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Verdict {
+    /// Actions that win the game from the given position
+    Solved(Vec<Action>),
+    /// Every reachable position was explored without finding a win
+    Unsolvable,
+    /// The node budget ran out first
+    Timeout,
+}
+
+/// Depth-first Klondike solver.
+#[derive(Debug, Clone)]
+pub struct Solver {
+    /// Maximum number of positions expanded before giving up
+    pub node_budget: usize,
+}
+impl Default for Solver {
+    fn default() -> Self {
+        Self {
+            node_budget: 200_000,
+        }
+    }
+}
+
+impl Solver {
+    pub fn new(node_budget: usize) -> Self {
+        Self { node_budget }
+    }
+
+    /// Searches for a winning sequence of actions from `state`, a Klondike position
+    pub fn solve(&self, state: &GameState) -> Result<Verdict, SolverError> {
+        if state.rules.variant != Variant::Klondike {
+            return Err(SolverError::UnsupportedVariant(state.rules.variant));
+        }
+        let mut search = Search {
+            visited: HashSet::new(),
+            path: vec![],
+            nodes: 0,
+            budget: self.node_budget,
+        };
+        Ok(match search.dfs(&mut state.clone()) {
+            Some(true) => Verdict::Solved(search.path),
+            Some(false) => Verdict::Unsolvable,
+            None => Verdict::Timeout,
+        })
+    }
+
+    /// Deals `seed` with `rules` and solves it from the start
+    pub fn solve_seed(&self, seed: u64, rules: RuleSet) -> Result<Verdict, SolverError> {
+        self.solve(&Game::with_rules(Some(seed), rules).state)
+    }
+}
+
+struct Search {
+    visited: HashSet<Vec<u8>>,
+    path: Vec<Action>,
+    nodes: usize,
+    budget: usize,
+}
+
+impl Search {
+    /// `Some(true)` if won, `Some(false)` if exhausted, `None` if out of budget.
+    ///
+    /// Moves are applied to `state` in place and reverted on the way back up.
+    fn dfs(&mut self, state: &mut GameState) -> Option<bool> {
+        if state.is_won() {
+            return Some(true);
+        }
+        if !self.visited.insert(state_key(state)) {
+            return Some(false);
+        }
+        self.nodes += 1;
+        if self.nodes > self.budget {
+            return None;
+        }
+
+        for action in candidate_moves(state) {
+            let Ok(record) = state.apply(&action) else {
+                continue;
+            };
+            self.path.push(action);
+            let result = self.dfs(state);
+            state.revert(&record).ok()?;
+            match result {
+                Some(true) => return Some(true),
+                Some(false) => {
+                    self.path.pop();
+                }
+                None => return None,
+            }
+        }
+        Some(false)
+    }
+}
@@ -0,0 +1,80 @@
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+use solitaire_solver::prelude::*;
+
+#[test]
+fn solutions_win_when_played() {
+    let Verdict::Solved(actions) = Solver::default()
+        .solve_seed(11, RuleSet::default())
+        .unwrap()
+    else {
+        panic!("seed 11 has a solution");
+    };
+    let mut game = Game::new(Some(11));
+    for action in actions {
+        game.handle_action(action).unwrap();
+    }
+    assert!(game.state.is_won());
+}
+
+#[test]
+fn sideways_shifts_do_not_eat_the_budget() {
+    // These ran out of budget while every run shift between columns was tried
+    for seed in [1, 33, 34, 35, 38] {
+        let verdict = Solver::default()
+            .solve_seed(seed, RuleSet::default())
+            .unwrap();
+        assert!(matches!(verdict, Verdict::Solved(_)), "seed {seed}");
+    }
+}
+
+#[test]
+fn budget_runs_out() {
+    assert!(matches!(
+        Solver::new(1).solve_seed(11, RuleSet::default()).unwrap(),
+        Verdict::Timeout
+    ));
+}
+
+#[test]
+fn stuck_positions_are_unsolvable() {
+    // The Queen of hearts sits on its Jack and no black King can take it
+    let rules = RuleSet {
+        foundation_to_tableau: false,
+        ..RuleSet::default()
+    };
+    let text = format!(
+        "rules: {}\n\
+         f0: Ah 2h 3h 4h 5h 6h 7h 8h 9h 10h\n\
+         f1: Ac 2c 3c 4c 5c 6c 7c 8c 9c 10c Jc Qc Kc\n\
+         f2: Ad 2d 3d 4d 5d 6d 7d 8d 9d 10d Jd Qd Kd\n\
+         f3: As 2s 3s 4s 5s 6s 7s 8s 9s 10s Js Qs Ks\n\
+         c0: Jh Qh\n\
+         c1: Kh\n\
+         c2:\n",
+        rules.to_notation()
+    );
+    let state: GameState = text.parse().unwrap();
+    assert!(state.violations().is_empty(), "{:?}", state.violations());
+    assert!(matches!(
+        Solver::default().solve(&state).unwrap(),
+        Verdict::Unsolvable
+    ));
+
+    // Taking the King of spades back down frees the Queen
+    let mut state = state;
+    state.rules.foundation_to_tableau = true;
+    assert!(matches!(
+        Solver::default().solve(&state).unwrap(),
+        Verdict::Solved(_)
+    ));
+}
+
+#[test]
+fn other_variants_are_refused() {
+    let rules = RuleSet::for_variant(Variant::FreeCell);
+    assert!(matches!(
+        Solver::default().solve_seed(1, rules),
+        Err(SolverError::UnsupportedVariant(Variant::FreeCell))
+    ));
+}
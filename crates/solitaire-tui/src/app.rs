@@ -0,0 +1,268 @@
+use getset::CopyGetters;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+
+/// Piles on the top row in cursor order, each with the column it is drawn above.
+///
+/// The stock and waste (or the free cells) sit on the left, the foundations on the right.
+pub fn top_row(state: &GameState) -> Vec<(PileId, u8)> {
+    let mut row = vec![];
+    if state.rules.variant.has_stock() {
+        row.push((PileId::Stock, 0));
+    }
+    if state.rules.variant.has_waste() {
+        row.push((PileId::Waste, 1));
+    }
+    row.extend(state.cell_ids().into_iter().zip(0..));
+    let foundations = state.foundation_ids();
+    let first = board_width(state).saturating_sub(foundations.len() as u8);
+    row.extend(foundations.into_iter().zip(first..));
+    row
+}
+/// Number of tableau columns
+pub fn n_columns(state: &GameState) -> u8 {
+    state.columns.len() as u8
+}
+/// Width of the board in piles, that of the widest pyramid or peaks row where there are no
+/// columns
+pub fn board_width(state: &GameState) -> u8 {
+    let variant = state.rules.variant;
+    match (0..variant.n_pyramid_slots())
+        .map(|i| variant.slot_position(i).1)
+        .max()
+    {
+        Some(half) => half / 2 + 1,
+        None => n_columns(state),
+    }
+}
+/// Pyramid slots the cursor can land on: those with a card free to play
+pub fn pyramid_stops(state: &GameState) -> Vec<PileId> {
+    state
+        .available_piles()
+        .into_iter()
+        .filter(|id| matches!(id, PileId::Pyramid(_)))
+        .collect()
+}
+
+/// Terminal front-end state: the game plus keyboard selection
+#[derive(CopyGetters)]
+pub struct App {
+    pub game: Game,
+    /// Pile under the cursor
+    #[getset(get_copy = "pub")]
+    cursor: PileId,
+    /// Pile picked up, its cards go onto the next pile chosen
+    #[getset(get_copy = "pub")]
+    selected: Option<PileId>,
+    /// Outcome of the last key press
+    message: String,
+    quit: bool,
+}
+
+impl App {
+    pub fn new(seed: Option<u64>) -> Self {
+        Self::from_game(Game::new(seed))
+    }
+    pub fn with_rules(seed: Option<u64>, rules: RuleSet) -> Self {
+        Self::from_game(Game::with_rules(seed, rules))
+    }
+    pub fn from_game(game: Game) -> Self {
+        // Stock, or the leftmost pile of games without one
+        let cursor = top_row(&game.state)
+            .first()
+            .map_or(PileId::Column(0), |(id, _)| *id);
+        Self {
+            game,
+            cursor,
+            selected: None,
+            message: String::new(),
+            quit: false,
+        }
+    }
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('c') if ctrl => self.quit = true,
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Esc => self.selected = None,
+
+            KeyCode::Left => self.move_cursor_sideways(-1),
+            KeyCode::Right => self.move_cursor_sideways(1),
+            KeyCode::Up | KeyCode::Down => self.switch_row(),
+            KeyCode::Char(c @ '1'..='9') if c as u8 - b'1' < n_columns(&self.game.state) => {
+                self.cursor = PileId::Column(c as u8 - b'1');
+            }
+            // The tenth Spider column
+            KeyCode::Char('0') if n_columns(&self.game.state) >= 10 => {
+                self.cursor = PileId::Column(9);
+            }
+
+            KeyCode::Enter | KeyCode::Char(' ') => self.choose(),
+            KeyCode::Char('f') => self.send_home(),
+            KeyCode::Char('d') => self.play(Action::Draw),
+            KeyCode::Char('r') if ctrl => self.play(Action::Redo),
+            KeyCode::Char('r') => self.play(Action::Recycle),
+            KeyCode::Char('u') | KeyCode::Char('z') => self.play(Action::Undo),
+            KeyCode::Char('y') => self.play(Action::Redo),
+            KeyCode::Char('h') => self.hint(),
+            KeyCode::Char('n') => {
+                *self = Self::from_game(Game::with_rules(None, self.game.state.rules));
+                self.message = "New game".to_string();
+            }
+            _ => {}
+        }
+    }
+
+    /// Left or right along the current row, wrapping around
+    fn move_cursor_sideways(&mut self, step: i32) {
+        let wrap = |i: usize, len: usize| (i as i32 + step).rem_euclid(len as i32) as usize;
+        let state = &self.game.state;
+        self.cursor = match self.cursor {
+            PileId::Column(i) => PileId::Column(wrap(i as usize, n_columns(state) as usize) as u8),
+            PileId::Pyramid(_) => {
+                let stops = pyramid_stops(state);
+                match stops.iter().position(|p| *p == self.cursor) {
+                    Some(i) => stops[wrap(i, stops.len())],
+                    None => stops.first().copied().unwrap_or(self.cursor),
+                }
+            }
+            top => {
+                let row = top_row(state);
+                let i = row.iter().position(|(p, _)| *p == top).unwrap_or(0);
+                row[wrap(i, row.len())].0
+            }
+        };
+    }
+    /// Jumps between the top row and the columns, to the pile drawn above or below
+    fn switch_row(&mut self) {
+        let row = top_row(&self.game.state);
+        if self.game.state.rules.variant.n_pyramid_slots() > 0 {
+            self.cursor = match self.cursor {
+                PileId::Pyramid(_) => row[0].0,
+                top => pyramid_stops(&self.game.state)
+                    .first()
+                    .copied()
+                    .unwrap_or(top),
+            };
+            return;
+        }
+        self.cursor = match self.cursor {
+            PileId::Column(i) => row
+                .iter()
+                .rev()
+                .find(|(_, slot)| *slot <= i)
+                .map_or(row[0].0, |(pile, _)| *pile),
+            top => {
+                let slot = row.iter().find(|(p, _)| *p == top).map_or(0, |(_, s)| *s);
+                PileId::Column(slot)
+            }
+        };
+    }
+
+    /// Enter on the cursor: deals from the stock, picks a pile up or puts it down
+    fn choose(&mut self) {
+        if self.cursor == PileId::Stock {
+            self.selected = None;
+            let state = &self.game.state;
+            let action = if state.stock.is_empty() && state.rules.variant.has_waste() {
+                Action::Recycle
+            } else {
+                Action::Draw
+            };
+            self.play(action);
+            return;
+        }
+        let variant = self.game.state.rules.variant;
+        // Golf and TriPeaks: the card under the cursor goes straight onto the waste
+        if variant.builds_on_waste()
+            && matches!(self.cursor, PileId::Column(_) | PileId::Pyramid(_))
+        {
+            self.selected = None;
+            self.play(Action::Move {
+                num_cards: 1,
+                from: self.cursor,
+                to: PileId::Waste,
+            });
+            return;
+        }
+        let pairs = variant.removes_pairs();
+        match self.selected.take() {
+            Some(from) if from == self.cursor => {}
+            Some(from) if pairs => self.play(Action::RemovePair {
+                first: from,
+                second: Some(self.cursor),
+            }),
+            Some(from) => self.move_onto(from, self.cursor),
+            // A King pairs with nothing, it goes at once
+            None if pairs
+                && self
+                    .game
+                    .state
+                    .available_card(self.cursor)
+                    .is_some_and(|card| is_pair(&card, None)) =>
+            {
+                self.play(Action::RemovePair {
+                    first: self.cursor,
+                    second: None,
+                })
+            }
+            None => {
+                if self
+                    .game
+                    .state
+                    .pile(self.cursor)
+                    .is_ok_and(|p| !p.is_empty())
+                {
+                    self.selected = Some(self.cursor);
+                }
+            }
+        }
+    }
+    /// Plays the legal move from `from` to `to`, the engine picking how many cards
+    fn move_onto(&mut self, from: PileId, to: PileId) {
+        let action = self.valid_move(|f, t| f == from && t == to);
+        match action {
+            Some(action) => self.play(action),
+            None => self.message = format!("Cannot move from {from} to {to}"),
+        }
+    }
+    /// Sends the top card of the selected pile (or the one under the cursor) home
+    fn send_home(&mut self) {
+        let from = self.selected.take().unwrap_or(self.cursor);
+        let action = self.valid_move(|f, t| f == from && matches!(t, PileId::Foundation(_)));
+        match action {
+            Some(action) => self.play(action),
+            None => self.message = format!("No card from {from} can go to a foundation"),
+        }
+    }
+    /// First move the engine found legal among those matching `wanted(from, to)`
+    fn valid_move(&self, wanted: impl Fn(PileId, PileId) -> bool) -> Option<Action> {
+        self.game
+            .actions_results()
+            .all_valid()
+            .into_iter()
+            .find(|action| matches!(action, Action::Move { from, to, .. } if wanted(*from, *to)))
+    }
+    fn hint(&mut self) {
+        self.message = match self.game.hint() {
+            Some(hint) => format!("Hint: {} ({})", hint.action, hint.reason),
+            None => "No useful move left".to_string(),
+        };
+    }
+
+    pub fn play(&mut self, action: Action) {
+        self.message = match self.game.handle_action(action) {
+            Ok(message) if self.game.state.is_won() => format!("{message}. You won!"),
+            Ok(message) => message,
+            Err(e) => e.to_string(),
+        };
+    }
+}
@@ -0,0 +1,6 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+pub mod app;
+pub mod ui;
+
+pub use app::App;
@@ -0,0 +1,92 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use std::{io, time::Duration};
+
+use enum_iterator::all;
+use ratatui::{
+    DefaultTerminal,
+    crossterm::event::{self, Event, KeyEventKind},
+};
+use solitaire_core::rules::{RuleSet, SuitCount, Variant};
+use solitaire_tui::{App, ui};
+
+const USAGE: &str = "Usage: solitaire-tui [--seed N] [--variant NAME] [--suits N] [--print]
+
+  --seed N          deal number N instead of a random deal
+  --variant NAME    klondike (default), freecell, spider, yukon, russian,
+                    pyramid, golf or tripeaks
+  --suits N         suits in a Spider deck: 1, 2 or 4 (default)
+  --print           print the dealt board once and exit, without taking over the terminal";
+
+/// Size of the board printed by `--print`
+const PRINT_SIZE: (u16, u16) = (100, 30);
+
+fn main() -> io::Result<()> {
+    let mut seed = None;
+    let mut variant = Variant::default();
+    let mut suits = SuitCount::default();
+    let mut print = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => match args.next().and_then(|s| s.parse().ok()) {
+                Some(n) => seed = Some(n),
+                None => usage_error("--seed needs a number"),
+            },
+            "--variant" => {
+                let name = args.next().unwrap_or_default();
+                match all::<Variant>().find(|v| v.to_string().eq_ignore_ascii_case(&name)) {
+                    Some(v) => variant = v,
+                    None => usage_error(&format!("unknown variant '{name}'")),
+                }
+            }
+            "--suits" => {
+                suits = match args.next().as_deref() {
+                    Some("1") => SuitCount::One,
+                    Some("2") => SuitCount::Two,
+                    Some("4") => SuitCount::Four,
+                    _ => usage_error("--suits needs 1, 2 or 4"),
+                }
+            }
+            "--print" => print = true,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            other => usage_error(&format!("unknown argument {other}")),
+        }
+    }
+
+    let rules = RuleSet {
+        suits,
+        ..RuleSet::for_variant(variant)
+    };
+    let mut app = App::with_rules(seed, rules);
+    if print {
+        print!("{}", ui::render_to_string(&app, PRINT_SIZE.0, PRINT_SIZE.1));
+        return Ok(());
+    }
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{message}\n\n{USAGE}");
+    std::process::exit(2)
+}
+
+fn run(terminal: &mut DefaultTerminal, app: &mut App) -> io::Result<()> {
+    while !app.should_quit() {
+        terminal.draw(|frame| ui::draw(frame, app))?;
+        // Wake up regularly so the clock keeps ticking
+        if event::poll(Duration::from_millis(250))?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            app.handle_key(key);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,264 @@
+use ratatui::{
+    Frame, Terminal,
+    backend::TestBackend,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+};
+use solitaire_core::prelude::*;
+
+use crate::app::{App, board_width, top_row};
+
+/// Back of a face-down card
+const CARD_BACK: &str = "░░░";
+const KEYS_HELP: &str = "←→ move  ↑↓ row  1-0 column  enter pick/drop  f foundation  \
+                         d draw  r recycle  u undo  y redo  h hint  n new  q quit";
+
+pub fn suit_glyph(suit: Suit) -> &'static str {
+    match suit {
+        Suit::Heart => "♥",
+        Suit::Club => "♣",
+        Suit::Diamond => "♦",
+        Suit::Spade => "♠",
+    }
+}
+
+fn card_back() -> Span<'static> {
+    Span::styled(CARD_BACK, Style::new().fg(Color::Blue))
+}
+
+/// Rank and suit glyph, red or in the terminal's own color, or the back if face down
+pub fn card_span(card: &Card) -> Span<'static> {
+    if *card.face() == Face::Down {
+        return card_back();
+    }
+    let text = format!("{}{}", card.rank(), suit_glyph(*card.suit()));
+    match card.color() {
+        solitaire_core::card::Color::Red => Span::styled(text, Style::new().fg(Color::Red)),
+        solitaire_core::card::Color::Black => Span::raw(text),
+    }
+}
+
+pub fn draw(frame: &mut Frame<'_>, app: &App) {
+    let [status, top, columns, message, help] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(3),
+        Constraint::Min(3),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    frame.render_widget(Paragraph::new(status_line(app)), status);
+    draw_top_row(frame, app, top);
+    if app.game.state.rules.variant.n_pyramid_slots() > 0 {
+        draw_pyramid(frame, app, columns);
+    } else {
+        draw_columns(frame, app, columns);
+    }
+    frame.render_widget(Paragraph::new(app.message().to_string()).bold(), message);
+    frame.render_widget(Paragraph::new(KEYS_HELP).dim(), help);
+}
+
+/// Draws one frame into a `width`×`height` buffer and returns its text, for headless use
+pub fn render_to_string(app: &App, width: u16, height: u16) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).expect("test backend");
+    terminal
+        .draw(|frame| draw(frame, app))
+        .expect("drawing to memory");
+    let buffer = terminal.backend().buffer();
+    let mut text = String::new();
+    for y in 0..buffer.area.height {
+        let line: String = (0..buffer.area.width)
+            .map(|x| buffer[(x, y)].symbol())
+            .collect();
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+fn status_line(app: &App) -> Line<'static> {
+    let game = &app.game;
+    let secs = game.elapsed().as_secs();
+    let rules = game.state.rules;
+    let mut variant = rules.variant.to_string();
+    if rules.variant == Variant::Spider {
+        variant.push_str(&format!(", {}", rules.suits));
+    }
+    if rules.variant.builds_on_waste() {
+        if rules.wrap {
+            variant.push_str(", King and Ace wrap");
+        }
+        if rules.variant == Variant::TriPeaks {
+            variant.push_str(&format!(", streak {}", game.timed_score().streak));
+        }
+    } else if rules.variant.has_waste() {
+        variant.push_str(&format!(" draw {}", rules.draw_count));
+        if let Some(n) = game.state.remaining_passes() {
+            variant.push_str(&format!(", {n} passes left"));
+        }
+    }
+    Line::from(format!(
+        "Seed {}  {variant}  Score: {}  Moves: {}  Time: {}:{:02}",
+        game.seed(),
+        game.timed_score(),
+        game.n_moves(),
+        secs / 60,
+        secs % 60,
+    ))
+}
+
+/// Frame of a pile, highlighted under the cursor and when picked up
+fn pile_block(app: &App, id: PileId, title: String) -> Block<'static> {
+    let style = if app.selected() == Some(id) {
+        Style::new().fg(Color::Green).add_modifier(Modifier::BOLD)
+    } else if app.cursor() == id {
+        Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+    } else {
+        Style::new().fg(Color::DarkGray)
+    };
+    Block::bordered().title(title).border_style(style)
+}
+
+/// Board split into one slot per column
+fn column_slots(app: &App, area: Rect) -> Vec<Rect> {
+    let n = board_width(&app.game.state).max(1);
+    Layout::horizontal(vec![Constraint::Ratio(1, n as u32); n as usize])
+        .split(area)
+        .to_vec()
+}
+
+/// Top row piles lined up with the columns, each widened up to the next one
+fn draw_top_row(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let state = &app.game.state;
+    let slots = column_slots(app, area);
+    let row = top_row(state);
+    for (i, (id, slot)) in row.iter().enumerate() {
+        let end = row.get(i + 1).map_or(*slot + 1, |(_, next)| *next);
+        let (Some(first), Some(last)) = (
+            slots.get(*slot as usize),
+            slots.get(end.saturating_sub(1).max(*slot) as usize),
+        ) else {
+            continue;
+        };
+        let content = match id {
+            PileId::Stock if state.stock.is_empty() => Line::from("↺").dim(),
+            PileId::Stock => Line::from(vec![
+                card_back(),
+                Span::raw(format!(" {}", state.stock.len())),
+            ]),
+            PileId::Waste => {
+                let shown = state.waste.fanned().max(1).min(state.waste.len());
+                let cards: Vec<Span<'static>> = state
+                    .waste
+                    .cards()
+                    .iter()
+                    .skip(state.waste.len() - shown)
+                    .flat_map(|card| [card_span(card), Span::raw(" ")])
+                    .collect();
+                Line::from(cards)
+            }
+            _ => match state.pile(*id).ok().and_then(|p| p.peek(Side::Top)) {
+                Some(card) => Line::from(card_span(&card)),
+                None => {
+                    let suit = match id {
+                        PileId::Foundation(n) => state.foundations[n].suit(),
+                        _ => None,
+                    };
+                    Line::from(suit.map_or("·", suit_glyph)).dim()
+                }
+            },
+        };
+        frame.render_widget(
+            Paragraph::new(content).block(pile_block(app, *id, id.label())),
+            first.union(*last),
+        );
+    }
+}
+
+/// One card per line, the top card last
+fn draw_columns(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let state = &app.game.state;
+    let slots = column_slots(app, area);
+    for (id, slot) in state.column_ids().into_iter().zip(slots.iter()) {
+        let PileId::Column(n) = id else { continue };
+        let lines: Vec<Line<'static>> = state.columns[&n]
+            .cards()
+            .iter()
+            .map(|card| Line::from(card_span(card)))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines).block(pile_block(app, id, (n + 1).to_string())),
+            *slot,
+        );
+    }
+}
+
+/// Width of a pyramid card, two of them side by side per slot of the row below
+const PYRAMID_CARD_WIDTH: usize = 6;
+
+/// Pyramid (or peaks) rows one per line, each card half a card off those it rests on.
+/// Covered cards are dimmed, the cursor and the picked card shown reversed.
+fn draw_pyramid(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let state = &app.game.state;
+    let variant = state.rules.variant;
+    let mut rows: Vec<Vec<(u8, u8)>> = vec![];
+    for i in 0..variant.n_pyramid_slots() {
+        let (row, half) = variant.slot_position(i);
+        if rows.len() <= row as usize {
+            rows.resize(row as usize + 1, vec![]);
+        }
+        rows[row as usize].push((half, i));
+    }
+    let lines: Vec<Line<'static>> = rows
+        .into_iter()
+        .map(|mut row| {
+            row.sort();
+            let mut spans = vec![];
+            let mut at = 0;
+            for (half, i) in row {
+                let start = half as usize * PYRAMID_CARD_WIDTH / 2;
+                spans.push(Span::raw(" ".repeat(start.saturating_sub(at))));
+                at = start + PYRAMID_CARD_WIDTH;
+                let id = PileId::Pyramid(i);
+                let mut span = match state.pyramid.get(&i).and_then(|s| s.peek(Side::Top)) {
+                    Some(card) => {
+                        let span = card_span(&card);
+                        let span = Span::styled(
+                            format!("{:^width$}", span.content, width = PYRAMID_CARD_WIDTH),
+                            span.style,
+                        );
+                        if state.is_uncovered(i) {
+                            span
+                        } else {
+                            span.dim()
+                        }
+                    }
+                    None => Span::raw(" ".repeat(PYRAMID_CARD_WIDTH)),
+                };
+                if app.selected() == Some(id) {
+                    span = span.fg(Color::Green).bold().reversed();
+                } else if app.cursor() == id {
+                    span = span.fg(Color::Yellow).bold().reversed();
+                }
+                spans.push(span);
+            }
+            Line::from(spans)
+        })
+        .collect();
+    let title = if variant.removes_pairs() {
+        "pyramid"
+    } else {
+        "peaks"
+    };
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::bordered()
+                .title(title)
+                .border_style(Style::new().fg(Color::DarkGray)),
+        ),
+        area,
+    );
+}
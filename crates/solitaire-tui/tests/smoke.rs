@@ -0,0 +1,144 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use solitaire_core::prelude::*;
+use solitaire_engine::prelude::*;
+use solitaire_tui::{App, ui};
+
+fn press(app: &mut App, code: KeyCode) {
+    app.handle_key(KeyEvent::from(code));
+}
+
+#[test]
+fn renders_the_deal() {
+    let app = App::new(Some(1));
+    let screen = ui::render_to_string(&app, 100, 30);
+    assert!(screen.contains("Seed 1"));
+    assert!(screen.contains("░░░ 24"), "{screen}");
+    // Column tops of deal 1
+    for card in ["9♠", "7♠", "4♠", "Q♠", "7♣", "A♠", "K♣"] {
+        assert!(screen.contains(card), "{card} missing from\n{screen}");
+    }
+}
+
+#[test]
+fn draw_undo_and_redo_from_the_keyboard() {
+    let mut app = App::new(Some(1));
+    press(&mut app, KeyCode::Char('d'));
+    assert_eq!(app.game.state.waste.len(), 1);
+    assert_eq!(app.game.state.stock.len(), 23);
+
+    press(&mut app, KeyCode::Char('u'));
+    assert!(app.game.state.waste.is_empty());
+    press(&mut app, KeyCode::Char('y'));
+    assert_eq!(app.game.state.waste.len(), 1);
+
+    // Enter on the stock draws too
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.game.state.waste.len(), 2);
+}
+
+#[test]
+fn picking_up_and_dropping_plays_a_legal_move() {
+    let mut app = App::new(Some(1));
+    // The ace on column 6 goes home with `f`
+    press(&mut app, KeyCode::Char('6'));
+    press(&mut app, KeyCode::Char('f'));
+    assert_eq!(app.game.n_moves(), 1);
+    assert_eq!(
+        app.game
+            .state
+            .foundations
+            .values()
+            .map(|f| f.len())
+            .sum::<usize>(),
+        1
+    );
+
+    // Any move between columns the engine allows, played with enter twice
+    let (from, to) = app
+        .game
+        .actions_results()
+        .all_valid()
+        .into_iter()
+        .find_map(|action| match action {
+            Action::Move {
+                from: PileId::Column(from),
+                to: PileId::Column(to),
+                ..
+            } => Some((from, to)),
+            _ => None,
+        })
+        .expect("deal 1 has a column move after the ace");
+    press(&mut app, KeyCode::Char((b'1' + from) as char));
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.selected(), Some(PileId::Column(from)));
+    press(&mut app, KeyCode::Char((b'1' + to) as char));
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.selected(), None);
+    assert_eq!(app.game.n_moves(), 2, "{}", app.message());
+}
+
+#[test]
+fn illegal_drops_leave_the_game_alone() {
+    let mut app = App::new(Some(1));
+    // 9♠ cannot go onto 7♠
+    press(&mut app, KeyCode::Char('1'));
+    press(&mut app, KeyCode::Enter);
+    press(&mut app, KeyCode::Char('2'));
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.game.n_moves(), 0);
+    assert!(app.message().starts_with("Cannot move"));
+}
+
+#[test]
+fn cursor_wraps_and_switches_rows() {
+    let mut app = App::new(Some(1));
+    press(&mut app, KeyCode::Left);
+    assert_eq!(app.cursor(), PileId::Foundation(3));
+    press(&mut app, KeyCode::Down);
+    assert_eq!(app.cursor(), PileId::Column(6));
+    press(&mut app, KeyCode::Right);
+    assert_eq!(app.cursor(), PileId::Column(0));
+    press(&mut app, KeyCode::Up);
+    assert_eq!(app.cursor(), PileId::Stock);
+
+    app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+    assert!(app.should_quit());
+}
+
+#[test]
+fn pyramid_cursor_steps_through_free_cards() {
+    let mut app = App::with_rules(Some(3), RuleSet::for_variant(Variant::Pyramid));
+    let screen = ui::render_to_string(&app, 100, 30);
+    assert!(screen.contains("pyramid"), "{screen}");
+    assert!(screen.contains("4♣"), "{screen}");
+
+    press(&mut app, KeyCode::Down);
+    assert_eq!(app.cursor(), PileId::Pyramid(21));
+    press(&mut app, KeyCode::Left);
+    assert_eq!(app.cursor(), PileId::Pyramid(27));
+    press(&mut app, KeyCode::Up);
+    assert_eq!(app.cursor(), PileId::Stock);
+
+    // J♣ and J♠ do not make thirteen
+    press(&mut app, KeyCode::Down);
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.selected(), Some(PileId::Pyramid(21)));
+    press(&mut app, KeyCode::Right);
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.game.n_moves(), 0);
+    assert_eq!(app.selected(), None);
+}
+
+#[test]
+fn enter_plays_a_peak_card_onto_the_waste() {
+    let mut app = App::with_rules(Some(3), RuleSet::for_variant(Variant::TriPeaks));
+    assert!(ui::render_to_string(&app, 100, 30).contains("peaks"));
+    press(&mut app, KeyCode::Down);
+    assert_eq!(app.cursor(), PileId::Pyramid(18));
+    press(&mut app, KeyCode::Right);
+    press(&mut app, KeyCode::Right);
+    // 5♠ onto 6♥
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.game.n_moves(), 1, "{}", app.message());
+    assert_eq!(app.game.timed_score().streak, 1);
+}